@@ -0,0 +1,152 @@
+//! IA local: minimax com poda alfa-beta sobre o `GameState`
+//!
+//! Cada ação (mover, atacar, finalizar turno) conta como um nível da busca.
+//! A busca usa aprofundamento iterativo: se o tempo acabar, devolve a melhor
+//! ação da última profundidade completa.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tatic_lib::{Action, GameState};
+use tracing::{debug, info};
+
+use crate::net::NetError;
+use crate::rules;
+
+/// Pontuação de vitória/derrota, acima de qualquer avaliação material
+const WIN_SCORE: i32 = 1_000_000;
+
+/// Quem escolhe as jogadas da IA numa partida
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiSource {
+    /// Endpoint `POST /ai/action` do servidor
+    #[default]
+    Server,
+    /// Busca local neste processo
+    Local,
+}
+
+/// Limites da busca
+#[derive(Debug, Clone, Copy)]
+pub struct AiLimits {
+    /// Profundidade máxima, em ações
+    pub depth: u32,
+    /// Tempo máximo de busca
+    pub time_limit: Duration,
+}
+
+/// Escolhe a ação de `player_id` sem bloquear o loop da interface
+pub async fn think(state: GameState, player_id: String, limits: AiLimits) -> Result<Action, NetError> {
+    tokio::task::spawn_blocking(move || choose_action(&state, &player_id, limits))
+        .await
+        .map_err(|e| NetError::Local(format!("IA local falhou: {}", e)))
+}
+
+/// Escolhe a melhor ação de `player_id` dentro dos limites
+pub fn choose_action(state: &GameState, player_id: &str, limits: AiLimits) -> Action {
+    let deadline = Instant::now() + limits.time_limit;
+    let mut best = Action::EndTurn;
+
+    for depth in 1..=limits.depth.max(1) {
+        match search_root(state, player_id, depth, deadline) {
+            Some((action, score)) => {
+                debug!("IA profundidade {}: {:?} ({})", depth, action, score);
+                best = action;
+            }
+            None => {
+                debug!("IA sem tempo na profundidade {}", depth);
+                break;
+            }
+        }
+    }
+
+    info!("🤖 IA local escolheu {:?}", best);
+    best
+}
+
+fn search_root(state: &GameState, player_id: &str, depth: u32, deadline: Instant) -> Option<(Action, i32)> {
+    let mut best: Option<(Action, i32)> = None;
+    let mut alpha = i32::MIN + 1;
+
+    for (action, next) in ordered_actions(state, player_id) {
+        let score = minimax(&next, player_id, depth - 1, alpha, i32::MAX, deadline)?;
+
+        if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+            best = Some((action, score));
+        }
+        alpha = alpha.max(score);
+    }
+
+    best
+}
+
+/// Devolve `None` quando o tempo acaba
+fn minimax(
+    state: &GameState,
+    ai_player: &str,
+    depth: u32,
+    mut alpha: i32,
+    mut beta: i32,
+    deadline: Instant,
+) -> Option<i32> {
+    if Instant::now() >= deadline {
+        return None;
+    }
+
+    if depth == 0 || rules::is_game_over(state) {
+        return Some(evaluate(state, ai_player));
+    }
+
+    let mover = state.turn.clone();
+    let maximizing = mover == ai_player;
+    let mut best = if maximizing { i32::MIN + 1 } else { i32::MAX };
+
+    for (_, next) in ordered_actions(state, &mover) {
+        let score = minimax(&next, ai_player, depth - 1, alpha, beta, deadline)?;
+
+        if maximizing {
+            best = best.max(score);
+            alpha = alpha.max(score);
+        } else {
+            best = best.min(score);
+            beta = beta.min(score);
+        }
+
+        if beta <= alpha {
+            break;
+        }
+    }
+
+    Some(best)
+}
+
+/// Ataques primeiro: costumam causar mais podas
+fn ordered_actions(state: &GameState, player_id: &str) -> Vec<(Action, GameState)> {
+    let mut actions = rules::legal_actions(state, player_id);
+    actions.sort_by_key(|(action, _)| match action {
+        Action::Attack { .. } => 0,
+        Action::Move { .. } => 1,
+        _ => 2,
+    });
+    actions
+}
+
+/// Vantagem material de `ai_player`: unidades e pontos de vida
+fn evaluate(state: &GameState, ai_player: &str) -> i32 {
+    if rules::is_game_over(state) {
+        return match rules::winner(state) {
+            Some(winner) if winner == ai_player => WIN_SCORE,
+            Some(_) => -WIN_SCORE,
+            None => 0,
+        };
+    }
+
+    rules::coords(&state.board)
+        .filter_map(|coord| state.board.get(&coord).and_then(|cell| cell.get_unit()))
+        .map(|unit| {
+            let value = 100 + unit.hp as i32;
+            if unit.owner == ai_player { value } else { -value }
+        })
+        .sum()
+}
@@ -0,0 +1,38 @@
+//! Animação da transição entre dois estados do tabuleiro
+
+use std::time::{Duration, Instant};
+
+use crate::diff::BoardChange;
+
+/// Transição em andamento
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// O que mudou entre o estado anterior e o atual
+    pub changes: Vec<BoardChange>,
+    started: Instant,
+    duration: Duration,
+    /// De 0.0 (início) a 1.0 (fim)
+    pub progress: f32,
+}
+
+impl Animation {
+    pub fn new(changes: Vec<BoardChange>, duration: Duration) -> Self {
+        Self {
+            changes,
+            started: Instant::now(),
+            duration,
+            progress: 0.0,
+        }
+    }
+
+    /// Avança até `now`; devolve `false` quando a animação terminou
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.started);
+        self.progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        self.progress < 1.0
+    }
+}
@@ -0,0 +1,108 @@
+//! DTOs do protocolo REST/WebSocket com o servidor
+//!
+//! Toda resposta REST vem no envelope `{success, data, error}`; os tipos
+//! abaixo descrevem o `data` de cada endpoint e os corpos das requisições.
+
+use serde::{Deserialize, Serialize};
+use tatic_lib::{Action, GamePhase, GameState};
+
+/// Envelope padrão das respostas REST
+#[derive(Debug, Deserialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    #[serde(default)]
+    pub data: Option<T>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Corpo de `POST /auth/login`
+#[derive(Debug, Serialize)]
+pub struct LoginRequest<'a> {
+    pub player_id: &'a str,
+    pub password: &'a str,
+}
+
+/// Resposta de `POST /auth/login`
+#[derive(Debug, Deserialize)]
+pub struct LoginResponse {
+    pub token: String,
+}
+
+/// Resumo de uma partida em `GET /matches`
+#[derive(Debug, Clone, Deserialize)]
+pub struct MatchSummary {
+    pub id: String,
+    pub players: Vec<String>,
+    pub phase: GamePhase,
+    pub turn_count: u32,
+}
+
+/// Corpo de `POST /match/create`
+#[derive(Debug, Serialize)]
+pub struct CreateMatchRequest<'a> {
+    pub player1: &'a str,
+    pub player2: &'a str,
+}
+
+/// Resposta de `POST /match/create` (o servidor devolve só o ID)
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub struct CreateMatchResponse {
+    pub match_id: String,
+}
+
+/// Query string de `GET /state`
+#[derive(Debug, Serialize)]
+pub struct StateQuery<'a> {
+    pub match_id: &'a str,
+}
+
+/// Corpo de `POST /action`
+#[derive(Debug, Serialize)]
+pub struct ActionRequest<'a> {
+    pub match_id: &'a str,
+    pub player_id: &'a str,
+    pub action: &'a Action,
+}
+
+/// Corpo de `POST /ai/action`
+#[derive(Debug, Serialize)]
+pub struct AiActionRequest<'a> {
+    pub match_id: &'a str,
+    pub ai_player: &'a str,
+}
+
+/// Mensagens enviadas pelo servidor no WebSocket
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerEvent {
+    StateUpdate { state: GameState },
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_envelope_decoding() {
+        let body = r#"{"success": true, "data": [{"id": "m1", "players": ["player1", "ai"], "phase": "InProgress", "turn_count": 3}]}"#;
+        let envelope: ApiResponse<Vec<MatchSummary>> = serde_json::from_str(body).unwrap();
+
+        assert!(envelope.success);
+        let matches = envelope.data.unwrap();
+        assert_eq!(matches[0].id, "m1");
+        assert_eq!(matches[0].players, vec!["player1", "ai"]);
+
+        // Fase com dados (partida encerrada) chega como objeto
+        let finished = r#"{"success": true, "data": [{"id": "m2", "players": ["player1", "ai"], "phase": {"Finished": {"winner": "ai"}}, "turn_count": 9}]}"#;
+        let envelope: ApiResponse<Vec<MatchSummary>> = serde_json::from_str(finished).unwrap();
+        assert!(matches!(envelope.data.unwrap()[0].phase, GamePhase::Finished { .. }));
+
+        // Campo renomeado no servidor vira erro de decodificação
+        let renamed = r#"{"success": true, "data": [{"match_id": "m1"}]}"#;
+        assert!(serde_json::from_str::<ApiResponse<Vec<MatchSummary>>>(renamed).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Autenticação: tela de login e armazenamento do token em disco
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use iced::{
+    widget::{button, column, container, row, text, text_input},
+    Alignment, Element, Length,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::Message;
+
+/// Sessão salva em disco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// Servidor que emitiu o token
+    pub server_url: String,
+    /// Jogador autenticado
+    pub player_id: String,
+    /// Bearer token
+    pub token: String,
+}
+
+/// Armazena a sessão em `<data_dir>/tatic_ascii_client/session.json`
+///
+/// Em sistemas Unix o arquivo fica com permissão 0600, para que só o próprio
+/// usuário consiga ler o token, mesmo que já existisse com outra permissão.
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    path: Option<PathBuf>,
+}
+
+impl TokenStore {
+    pub fn new() -> Self {
+        Self {
+            path: dirs::data_dir().map(|dir| dir.join("tatic_ascii_client").join("session.json")),
+        }
+    }
+
+    /// Sem arquivo: nada é lido nem gravado
+    #[cfg(test)]
+    pub fn disabled() -> Self {
+        Self { path: None }
+    }
+
+    /// Carrega a sessão salva para este servidor e jogador, se houver
+    pub fn load(&self, server_url: &str, player_id: &str) -> Option<Session> {
+        let contents = std::fs::read_to_string(self.path.as_ref()?).ok()?;
+
+        match serde_json::from_str::<Session>(&contents) {
+            Ok(session) if session.server_url == server_url && session.player_id == player_id => {
+                Some(session)
+            }
+            Ok(_) => {
+                debug!("Sessão salva pertence a outro servidor ou jogador");
+                None
+            }
+            Err(e) => {
+                warn!("⚠️ Sessão salva inválida: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Salva a sessão, substituindo a anterior
+    pub fn save(&self, session: &Session) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        // `mode` só vale para arquivos novos: um arquivo antigo mantém a
+        // permissão que tinha, então ela é corrigida antes de gravar o token
+        let mut file = options.open(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(serde_json::to_string(session)?.as_bytes())
+    }
+
+    /// Apaga a sessão salva (logout ou token expirado)
+    pub fn clear(&self) {
+        if let Some(path) = &self.path {
+            if let Err(e) = std::fs::remove_file(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    warn!("⚠️ Erro ao apagar sessão: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Estado da tela de login
+#[derive(Debug, Clone, Default)]
+pub struct Login {
+    pub player_id: String,
+    pub password: String,
+    /// Há um login em andamento
+    pub busy: bool,
+}
+
+impl Login {
+    pub fn new(player_id: String) -> Self {
+        Self {
+            player_id,
+            ..Self::default()
+        }
+    }
+
+    /// Renderiza o formulário de login
+    pub fn view(&self) -> Element<'_, Message> {
+        let can_submit = !self.busy && !self.player_id.trim().is_empty() && !self.password.is_empty();
+        let submit = can_submit.then_some(Message::SubmitLogin);
+
+        let form = column![
+            text("Entrar").size(20),
+            row![
+                text("Jogador").width(80),
+                text_input("ID do jogador", &self.player_id)
+                    .on_input(Message::LoginPlayerChanged)
+                    .width(250),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            row![
+                text("Senha").width(80),
+                text_input("Senha", &self.password)
+                    .on_input(Message::LoginPasswordChanged)
+                    .on_submit_maybe(submit.clone())
+                    .secure(true)
+                    .width(250),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            button(if self.busy { "Entrando..." } else { "Entrar" }).on_press_maybe(submit),
+        ]
+        .spacing(15);
+
+        container(form)
+            .width(Length::Fill)
+            .center_x(Length::Fill)
+            .padding(10)
+            .into()
+    }
+}
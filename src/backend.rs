@@ -0,0 +1,119 @@
+//! Abstração da fonte de partidas usada pela interface
+//!
+//! `RpgAsciiClient` só conversa com um `GameBackend`: o servidor REST/WebSocket
+//! (`NetworkClient`) ou uma engine local (`LocalBackend`), que permite usar e
+//! testar a interface sem servidor rodando.
+
+use futures::future::{BoxFuture, FutureExt};
+use iced::Subscription;
+use tatic_lib::{Action, GameState};
+
+use crate::api::MatchSummary;
+use crate::net::{NetError, NetworkClient};
+use crate::{ws, Message};
+
+/// Operações de partida que a interface precisa
+///
+/// Os futures são `'static` para poderem ir direto para `Task::perform`.
+pub trait GameBackend: std::fmt::Debug + Send + Sync {
+    /// Lista as partidas disponíveis
+    fn list_matches(&self) -> BoxFuture<'static, Result<Vec<MatchSummary>, NetError>>;
+
+    /// Cria uma partida e devolve seu ID
+    fn create_match(
+        &self,
+        player1: String,
+        player2: String,
+    ) -> BoxFuture<'static, Result<String, NetError>>;
+
+    /// Entra numa partida existente e devolve seu ID
+    fn join_match(
+        &self,
+        match_id: String,
+        player_id: String,
+    ) -> BoxFuture<'static, Result<String, NetError>>;
+
+    /// Obtém o estado atual da partida
+    fn get_state(&self, match_id: String) -> BoxFuture<'static, Result<GameState, NetError>>;
+
+    /// Aplica uma ação e devolve o novo estado
+    fn send_action(
+        &self,
+        match_id: String,
+        player_id: String,
+        action: Action,
+    ) -> BoxFuture<'static, Result<GameState, NetError>>;
+
+    /// Pede à IA a próxima ação de `ai_player`, sem aplicá-la
+    ///
+    /// Nas partidas do servidor, quem joga pelos participantes de IA é o
+    /// próprio servidor, que publica o novo estado pelo WebSocket; lá esta
+    /// chamada só serve de sugestão para o jogador. O cliente só envia ações
+    /// em nome da IA nas partidas da engine local.
+    fn get_ai_action(
+        &self,
+        match_id: String,
+        ai_player: String,
+    ) -> BoxFuture<'static, Result<Action, NetError>>;
+
+    /// Atualizações ao vivo da partida
+    fn subscribe(&self, match_id: &str) -> Subscription<Message>;
+}
+
+impl GameBackend for NetworkClient {
+    fn list_matches(&self) -> BoxFuture<'static, Result<Vec<MatchSummary>, NetError>> {
+        let network = self.clone();
+        async move { network.list_matches().await }.boxed()
+    }
+
+    fn create_match(
+        &self,
+        player1: String,
+        player2: String,
+    ) -> BoxFuture<'static, Result<String, NetError>> {
+        let network = self.clone();
+        async move { network.create_match(&player1, &player2).await }.boxed()
+    }
+
+    fn join_match(
+        &self,
+        match_id: String,
+        _player_id: String,
+    ) -> BoxFuture<'static, Result<String, NetError>> {
+        // Não há endpoint de join: basta a partida existir
+        let network = self.clone();
+        async move {
+            network.get_state(&match_id).await?;
+            Ok(match_id)
+        }
+        .boxed()
+    }
+
+    fn get_state(&self, match_id: String) -> BoxFuture<'static, Result<GameState, NetError>> {
+        let network = self.clone();
+        async move { network.get_state(&match_id).await }.boxed()
+    }
+
+    fn send_action(
+        &self,
+        match_id: String,
+        player_id: String,
+        action: Action,
+    ) -> BoxFuture<'static, Result<GameState, NetError>> {
+        let network = self.clone();
+        async move { network.send_action(&match_id, &player_id, action).await }.boxed()
+    }
+
+    fn get_ai_action(
+        &self,
+        match_id: String,
+        ai_player: String,
+    ) -> BoxFuture<'static, Result<Action, NetError>> {
+        let network = self.clone();
+        async move { network.get_ai_action(&match_id, &ai_player).await }.boxed()
+    }
+
+    fn subscribe(&self, match_id: &str) -> Subscription<Message> {
+        ws::subscribe(self.ws_url(match_id), self.token().map(str::to_string))
+    }
+}
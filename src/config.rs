@@ -0,0 +1,337 @@
+//! Configuração do cliente
+//!
+//! Cada campo é resolvido na seguinte ordem de precedência:
+//! 1. flags de linha de comando (`--server`, `--player`, `--log`, `--opponent`, `--offline`,
+//!    `--debug-actions`)
+//! 2. variáveis de ambiente (`TATIC_SERVER_URL`, `TATIC_PLAYER_ID`, `TATIC_LOG`, `TATIC_OPPONENT`)
+//! 3. arquivo TOML (`<config_dir>/tatic_ascii_client/config.toml` ou `--config`)
+//! 4. valores padrão
+//!
+//! Exemplo de `config.toml`:
+//!
+//! ```toml
+//! server_url = "http://192.168.0.10:3000"
+//! player_id = "maria"
+//! log_filter = "info,client=debug"
+//! default_opponent = "ai"
+//! connect_timeout_secs = 5
+//! read_timeout_secs = 10
+//! max_retries = 3
+//! offline = false
+//! ai_source = "server"
+//! ai_depth = 3
+//! ai_time_limit_ms = 1500
+//! ai_players = ["ai"]
+//! ai_auto_play = true
+//! ai_move_delay_ms = 600
+//! debug_actions = false
+//! theme = "dark"  # dark, light, high_contrast ou colorblind
+//! animations = true
+//! animation_ms = 350
+//!
+//! [keys]
+//! preset = "arrows"
+//! ```
+//!
+//! Os atalhos de teclado estão documentados em [`crate::keymap`]. A paleta
+//! escolhida na interface é gravada de volta no mesmo arquivo.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+
+use crate::ai::{AiLimits, AiSource};
+use crate::keymap::{KeyConfig, Keymap};
+use crate::net::RequestPolicy;
+use crate::theme::PaletteName;
+
+/// Argumentos de linha de comando
+#[derive(Debug, Default, Parser)]
+#[command(name = "client", about = "Cliente GUI do RPG ASCII Tático")]
+pub struct Cli {
+    /// URL base do servidor (ex.: http://localhost:3000)
+    #[arg(long)]
+    pub server: Option<String>,
+    /// ID do jogador local
+    #[arg(long)]
+    pub player: Option<String>,
+    /// Filtro de log no formato do `tracing` (ex.: info,client=debug)
+    #[arg(long)]
+    pub log: Option<String>,
+    /// Oponente padrão ao criar partidas (ID de jogador ou "ai")
+    #[arg(long)]
+    pub opponent: Option<String>,
+    /// Caminho alternativo para o arquivo de configuração
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// Joga com a engine local, sem servidor
+    #[arg(long)]
+    pub offline: bool,
+    /// Permite enviar ações recusadas pela validação local (depuração)
+    #[arg(long)]
+    pub debug_actions: bool,
+}
+
+/// Configuração efetiva do cliente
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// URL base do servidor REST/WebSocket
+    pub server_url: String,
+    /// ID do jogador local
+    pub player_id: String,
+    /// Filtro de log do `tracing`
+    pub log_filter: String,
+    /// Oponente usado ao criar partidas
+    pub default_opponent: String,
+    /// Timeout de conexão com o servidor, em segundos
+    pub connect_timeout_secs: u64,
+    /// Timeout de leitura das respostas, em segundos
+    pub read_timeout_secs: u64,
+    /// Repetições de chamadas idempotentes (listar partidas, obter estado)
+    pub max_retries: u32,
+    /// Usa a engine local em vez do servidor
+    pub offline: bool,
+    /// IA padrão das partidas ("server" ou "local")
+    pub ai_source: AiSource,
+    /// Profundidade máxima da IA local, em ações
+    pub ai_depth: u32,
+    /// Tempo máximo de busca da IA local, em milissegundos
+    pub ai_time_limit_ms: u64,
+    /// Participantes controlados pela IA (nas partidas do servidor, é o
+    /// servidor quem joga por eles)
+    pub ai_players: Vec<String>,
+    /// Joga os turnos da IA sem precisar clicar em "IA Jogar"
+    pub ai_auto_play: bool,
+    /// Espera entre jogadas automáticas da IA, em milissegundos
+    pub ai_move_delay_ms: u64,
+    /// Atalhos de teclado
+    pub keys: KeyConfig,
+    /// Oferece "Enviar mesmo assim" para ações recusadas localmente
+    pub debug_actions: bool,
+    /// Paleta de cores
+    pub theme: PaletteName,
+    /// Anima as mudanças entre estados do tabuleiro
+    pub animations: bool,
+    /// Duração das animações, em milissegundos
+    pub animation_ms: u64,
+    /// Arquivo de onde a configuração foi lida (e onde a paleta é salva)
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server_url: "http://localhost:3000".to_string(),
+            player_id: "player1".to_string(),
+            log_filter: "info,client=debug".to_string(),
+            default_opponent: "ai".to_string(),
+            connect_timeout_secs: 5,
+            read_timeout_secs: 10,
+            max_retries: 3,
+            offline: false,
+            ai_source: AiSource::Server,
+            ai_depth: 3,
+            ai_time_limit_ms: 1500,
+            ai_players: vec!["ai".to_string()],
+            ai_auto_play: true,
+            ai_move_delay_ms: 600,
+            keys: KeyConfig::default(),
+            debug_actions: false,
+            theme: PaletteName::default(),
+            animations: true,
+            animation_ms: 350,
+            path: None,
+        }
+    }
+}
+
+impl Config {
+    /// Monta a configuração a partir dos flags, ambiente, arquivo e padrões
+    pub fn load(cli: Cli) -> Result<Self> {
+        let path = match &cli.config {
+            Some(path) => Some(path.clone()),
+            None => default_path(),
+        };
+
+        let mut config = match &path {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+        config.path = path;
+
+        config.apply_env(|name| std::env::var(name).ok());
+        config.apply_cli(cli);
+
+        // Atalhos inválidos são erro de configuração, não surpresa durante o jogo
+        config.keymap()?;
+
+        Ok(config)
+    }
+
+    /// Timeouts e retries do cliente HTTP
+    pub fn request_policy(&self) -> RequestPolicy {
+        RequestPolicy {
+            connect_timeout: Duration::from_secs(self.connect_timeout_secs),
+            read_timeout: Duration::from_secs(self.read_timeout_secs),
+            max_retries: self.max_retries,
+        }
+    }
+
+    /// Tabela de atalhos do teclado
+    pub fn keymap(&self) -> Result<Keymap> {
+        Keymap::from_config(&self.keys).context("Atalhos de teclado inválidos em [keys]")
+    }
+
+    /// Limites de busca da IA local
+    pub fn ai_limits(&self) -> AiLimits {
+        AiLimits {
+            depth: self.ai_depth,
+            time_limit: Duration::from_millis(self.ai_time_limit_ms),
+        }
+    }
+
+    /// Lê o arquivo TOML; arquivo inexistente equivale aos padrões
+    pub fn from_file(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Arquivo de configuração inválido: {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e)
+                .with_context(|| format!("Erro ao ler configuração: {}", path.display())),
+        }
+    }
+
+    /// Grava a paleta atual no arquivo de configuração
+    ///
+    /// Edita só a chave `theme` no documento lido do disco: comentários,
+    /// formatação e valores vindos de flags ou do ambiente ficam como estão.
+    pub fn save_theme(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            bail!("Sem diretório de configuração para salvar o tema");
+        };
+
+        let mut document = match std::fs::read_to_string(path) {
+            Ok(contents) => contents
+                .parse::<DocumentMut>()
+                .with_context(|| format!("Arquivo de configuração inválido: {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => DocumentMut::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Erro ao ler configuração: {}", path.display()));
+            }
+        };
+        document["theme"] = toml_edit::value(self.theme.key());
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Erro ao criar {}", dir.display()))?;
+        }
+        std::fs::write(path, document.to_string())
+            .with_context(|| format!("Erro ao salvar configuração: {}", path.display()))
+    }
+
+    /// Sobrescreve campos com as variáveis de ambiente definidas
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) {
+        if let Some(value) = var("TATIC_SERVER_URL") {
+            self.server_url = value;
+        }
+        if let Some(value) = var("TATIC_PLAYER_ID") {
+            self.player_id = value;
+        }
+        if let Some(value) = var("TATIC_LOG") {
+            self.log_filter = value;
+        }
+        if let Some(value) = var("TATIC_OPPONENT") {
+            self.default_opponent = value;
+        }
+    }
+
+    /// Sobrescreve campos com os flags passados na linha de comando
+    pub fn apply_cli(&mut self, cli: Cli) {
+        if let Some(value) = cli.server {
+            self.server_url = value;
+        }
+        if let Some(value) = cli.player {
+            self.player_id = value;
+        }
+        if let Some(value) = cli.log {
+            self.log_filter = value;
+        }
+        if let Some(value) = cli.opponent {
+            self.default_opponent = value;
+        }
+        if cli.offline {
+            self.offline = true;
+        }
+        if cli.debug_actions {
+            self.debug_actions = true;
+        }
+    }
+}
+
+/// Caminho padrão do arquivo de configuração
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("tatic_ascii_client").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_precedence() {
+        let mut config: Config = toml::from_str(
+            "server_url = \"http://file:3000\"\nplayer_id = \"file\"\ndefault_opponent = \"bob\"",
+        )
+        .unwrap();
+        assert_eq!(config.log_filter, Config::default().log_filter);
+
+        config.apply_env(|name| match name {
+            "TATIC_PLAYER_ID" => Some("env".to_string()),
+            "TATIC_OPPONENT" => Some("env_opponent".to_string()),
+            _ => None,
+        });
+        config.apply_cli(Cli {
+            player: Some("cli".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(config.server_url, "http://file:3000");
+        assert_eq!(config.player_id, "cli");
+        assert_eq!(config.default_opponent, "env_opponent");
+    }
+
+    #[test]
+    fn test_theme_saved_with_read_modify_write() {
+        // Diretório próprio: testes em paralelo não disputam o mesmo arquivo
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("tatic_theme_{}_{}", std::process::id(), nanos));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "# meu servidor\nplayer_id = \"file\"\ntheme = \"dark\"\n").unwrap();
+
+        // Valor vindo de flag não deve ir para o arquivo
+        let config = Config {
+            player_id: "cli".to_string(),
+            theme: PaletteName::HighContrast,
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+        config.save_theme().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let saved = Config::from_file(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(contents.starts_with("# meu servidor\n"));
+        assert_eq!(saved.theme, PaletteName::HighContrast);
+        assert_eq!(saved.player_id, "file");
+    }
+}
@@ -0,0 +1,206 @@
+//! Diferenças entre dois tabuleiros consecutivos
+//!
+//! O servidor manda só estados completos, então o que aconteceu é inferido
+//! comparando as unidades de cada casa. Unidades não têm ID: uma unidade que
+//! some de uma casa e aparece em outra, com o mesmo dono e símbolo, é
+//! considerada a mesma (a mais próxima, se houver mais de uma candidata).
+
+use tatic_lib::{Board, Coord, GameState};
+
+use crate::rules;
+
+/// Unidade como vista numa casa
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitSnapshot {
+    pub owner: String,
+    pub symbol: char,
+    pub hp: i64,
+}
+
+/// Uma mudança entre os dois tabuleiros
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardChange {
+    Moved {
+        unit: UnitSnapshot,
+        from: Coord,
+        to: Coord,
+    },
+    Damaged {
+        unit: UnitSnapshot,
+        at: Coord,
+        hp_lost: i64,
+    },
+    Destroyed {
+        unit: UnitSnapshot,
+        at: Coord,
+    },
+}
+
+/// Mudanças de `old` para `new`: movimentos, dano e unidades destruídas
+pub fn diff_boards(old: &Board, new: &Board) -> Vec<BoardChange> {
+    let before = units(old);
+    let after = units(new);
+    let mut changes = Vec::new();
+
+    // Casas cuja unidade saiu (ou foi trocada) e casas com unidade nova
+    let mut vanished: Vec<&(Coord, UnitSnapshot)> = Vec::new();
+    let mut appeared: Vec<&(Coord, UnitSnapshot)> = Vec::new();
+
+    for entry in &before {
+        match after.iter().find(|(coord, _)| *coord == entry.0) {
+            Some((_, unit)) if same_unit(unit, &entry.1) => {
+                if unit.hp < entry.1.hp {
+                    changes.push(BoardChange::Damaged {
+                        unit: unit.clone(),
+                        at: entry.0,
+                        hp_lost: entry.1.hp - unit.hp,
+                    });
+                }
+            }
+            _ => vanished.push(entry),
+        }
+    }
+    for entry in &after {
+        let was_there = before
+            .iter()
+            .any(|(coord, unit)| *coord == entry.0 && same_unit(unit, &entry.1));
+        if !was_there {
+            appeared.push(entry);
+        }
+    }
+
+    for (from, unit) in vanished {
+        let closest = appeared
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, candidate))| same_unit(candidate, unit))
+            .min_by_key(|(_, (to, _))| distance(*from, *to))
+            .map(|(index, _)| index);
+
+        match closest {
+            Some(index) => {
+                let (to, moved) = appeared.remove(index);
+                changes.push(BoardChange::Moved {
+                    unit: moved.clone(),
+                    from: *from,
+                    to: *to,
+                });
+            }
+            None => changes.push(BoardChange::Destroyed {
+                unit: unit.clone(),
+                at: *from,
+            }),
+        }
+    }
+
+    changes
+}
+
+/// Origem e destino da última ação de `actor`, a partir do estado
+/// `previous` em que ela foi feita
+///
+/// Um movimento dá as duas casas diretamente. Num ataque só o alvo muda: o
+/// atacante só é apontado se for a única unidade de `actor` que alcançava o
+/// alvo; com mais de uma candidata, a origem fica desconhecida.
+pub fn last_move(changes: &[BoardChange], actor: &str, previous: &GameState) -> Option<(Coord, Coord)> {
+    let moved = changes.iter().find_map(|change| match change {
+        BoardChange::Moved { unit, from, to } if unit.owner == actor => Some((*from, *to)),
+        _ => None,
+    });
+    if moved.is_some() {
+        return moved;
+    }
+
+    let target = changes.iter().find_map(|change| match change {
+        BoardChange::Damaged { unit, at, .. } | BoardChange::Destroyed { unit, at }
+            if unit.owner != actor =>
+        {
+            Some(*at)
+        }
+        _ => None,
+    })?;
+    let mut attackers = units(&previous.board)
+        .into_iter()
+        .filter(|(_, unit)| unit.owner == actor)
+        .map(|(coord, _)| coord)
+        .filter(|&coord| rules::targets(previous, actor, coord).attacks.contains(&target));
+
+    match (attackers.next(), attackers.next()) {
+        (Some(attacker), None) => Some((attacker, target)),
+        _ => None,
+    }
+}
+
+/// Resumo legível das mudanças, ou `None` se nada mudou
+pub fn summarize(changes: &[BoardChange]) -> Option<String> {
+    let parts: Vec<String> = changes
+        .iter()
+        .map(|change| match change {
+            BoardChange::Moved { unit, from, to } => {
+                format!("{} moveu {}→{}", unit.symbol, position(*from), position(*to))
+            }
+            BoardChange::Damaged { unit, at, hp_lost } => {
+                format!("{} em {} perdeu {} HP", unit.symbol, position(*at), hp_lost)
+            }
+            BoardChange::Destroyed { unit, at } => {
+                format!("{} em {} foi destruído", unit.symbol, position(*at))
+            }
+        })
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+fn position(coord: Coord) -> String {
+    format!("({},{})", coord.x, coord.y)
+}
+
+fn units(board: &Board) -> Vec<(Coord, UnitSnapshot)> {
+    rules::coords(board)
+        .filter_map(|coord| {
+            let unit = board.get(&coord)?.get_unit()?;
+            Some((
+                coord,
+                UnitSnapshot {
+                    owner: unit.owner.clone(),
+                    symbol: unit.symbol,
+                    hp: unit.hp as i64,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn same_unit(a: &UnitSnapshot, b: &UnitSnapshot) -> bool {
+    a.owner == b.owner && a.symbol == b.symbol
+}
+
+fn distance(a: Coord, b: Coord) -> usize {
+    a.x.abs_diff(b.x) + a.y.abs_diff(b.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tatic_lib::Action;
+
+    #[test]
+    fn test_board_diff_finds_last_move() {
+        let state = GameState::new("player1", "player2");
+        let (action, next) = rules::legal_actions(&state, "player1")
+            .into_iter()
+            .find(|(action, _)| matches!(action, Action::Move { .. }))
+            .unwrap();
+        let Action::Move { from, to } = action else { unreachable!() };
+
+        let changes = diff_boards(&state.board, &next.board);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(last_move(&changes, "player1", &state), Some((from, to)));
+
+        let summary = summarize(&changes).unwrap();
+        assert!(summary.contains(&format!("({},{})→({},{})", from.x, from.y, to.x, to.y)));
+
+        // Mesmo estado duas vezes não gera resumo
+        assert!(diff_boards(&next.board, &next.board).is_empty());
+    }
+}
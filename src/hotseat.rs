@@ -0,0 +1,52 @@
+//! Hot-seat: dois jogadores no mesmo teclado, sem servidor
+
+use iced::{
+    widget::{button, column, container, text},
+    Alignment, Element, Length,
+};
+
+use crate::Message;
+
+/// Estado de uma partida hot-seat em andamento
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    /// Os dois jogadores locais
+    pub players: [String; 2],
+    /// Jogador configurado antes da partida, restaurado ao sair
+    pub home_player: String,
+    /// Jogador que deve assumir o teclado (tela de troca visível)
+    pub handoff_to: Option<String>,
+}
+
+impl HotSeat {
+    pub fn new(player1: String, player2: String, home_player: String) -> Self {
+        Self {
+            players: [player1, player2],
+            home_player,
+            handoff_to: None,
+        }
+    }
+
+    /// Verdadeiro se `player_id` joga nesta partida
+    pub fn has_player(&self, player_id: &str) -> bool {
+        self.players.iter().any(|player| player == player_id)
+    }
+
+    /// Tela entre turnos, que esconde o tabuleiro e a seleção do outro lado
+    pub fn handoff_view(player: &str) -> Element<'_, Message> {
+        container(
+            column![
+                text("Troca de turno").size(24),
+                text(format!("Passe o teclado para {}", player)).size(18),
+                button("Pronto").on_press(Message::HandoffReady),
+            ]
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .height(400)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+}
@@ -1,11 +1,15 @@
-use tatic_lib::Coord;
-use iced::keyboard::{self, Key};
+use tatic_lib::{Action, Coord};
+use iced::keyboard;
 use iced::Event;
 
+use crate::keymap::{Command, Keymap};
+
 /// Estado do input do teclado
 #[derive(Debug, Clone)]
 pub struct InputState {
     pub cursor: Coord,
+    /// Largura e altura do tabuleiro atual, limites do cursor
+    pub board_size: (usize, usize),
     pub shift_pressed: bool,
     pub ctrl_pressed: bool,
 }
@@ -14,53 +18,75 @@ impl InputState {
     pub fn new() -> Self {
         Self {
             cursor: Coord::new(0, 0),
+            board_size: (8, 8),
             shift_pressed: false,
             ctrl_pressed: false,
         }
     }
     
-    /// Processa evento de teclado
-    pub fn handle_keyboard_event(&mut self, event: Event) -> Option<crate::Message> {
+    /// Ajusta os limites ao tabuleiro, trazendo o cursor para dentro dele
+    pub fn set_board_size(&mut self, (width, height): (usize, usize)) {
+        self.board_size = (width, height);
+        self.cursor.x = self.cursor.x.min(width.saturating_sub(1));
+        self.cursor.y = self.cursor.y.min(height.saturating_sub(1));
+    }
+    
+    /// Coloca o cursor em `coord` (por exemplo, após um clique)
+    pub fn move_cursor_to(&mut self, coord: Coord) {
+        self.cursor = coord;
+    }
+    
+    /// Processa evento de teclado conforme os atalhos configurados
+    pub fn handle_keyboard_event(&mut self, event: Event, keymap: &Keymap) -> Option<crate::Message> {
         match event {
-            Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => {
-                match key {
-                    Key::Character(c) => match c.as_str() {
-                        "w" | "W" => {
-                            if self.cursor.y > 0 {
-                                self.cursor.y -= 1;
-                            }
-                            Some(crate::Message::KeyPressed('w'))
-                        }
-                        "s" | "S" => {
-                            if self.cursor.y < 7 {
-                                self.cursor.y += 1;
-                            }
-                            Some(crate::Message::KeyPressed('s'))
-                        }
-                        "a" | "A" => {
-                            if self.cursor.x > 0 {
-                                self.cursor.x -= 1;
-                            }
-                            Some(crate::Message::KeyPressed('a'))
-                        }
-                        "d" | "D" => {
-                            if self.cursor.x < 7 {
-                                self.cursor.x += 1;
-                            }
-                            Some(crate::Message::KeyPressed('d'))
-                        }
-                        _ => None,
-                    }
-                    Key::Named(keyboard::key::Named::Enter) => {
-                        Some(crate::Message::CellClicked(self.cursor))
-                    }
-                    Key::Named(keyboard::key::Named::Escape) => {
-                        Some(crate::Message::ClearSelection)
-                    }
-                    _ => None,
-                }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                let command = keymap.command_for(&key, modifiers)?;
+                self.apply_command(command)
+            }
+            // Também vale para cliques: Shift+clique ataca, Ctrl+clique move
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                self.shift_pressed = modifiers.shift();
+                self.ctrl_pressed = modifiers.control();
+                None
             }
             _ => None,
         }
     }
+    
+    /// Executa o comando: move o cursor ou gera a mensagem correspondente
+    pub fn apply_command(&mut self, command: Command) -> Option<crate::Message> {
+        match command {
+            Command::CursorUp => {
+                if self.cursor.y > 0 {
+                    self.cursor.y -= 1;
+                }
+                Some(crate::Message::CursorMoved(self.cursor))
+            }
+            Command::CursorDown => {
+                if self.cursor.y + 1 < self.board_size.1 {
+                    self.cursor.y += 1;
+                }
+                Some(crate::Message::CursorMoved(self.cursor))
+            }
+            Command::CursorLeft => {
+                if self.cursor.x > 0 {
+                    self.cursor.x -= 1;
+                }
+                Some(crate::Message::CursorMoved(self.cursor))
+            }
+            Command::CursorRight => {
+                if self.cursor.x + 1 < self.board_size.0 {
+                    self.cursor.x += 1;
+                }
+                Some(crate::Message::CursorMoved(self.cursor))
+            }
+            Command::Select => Some(crate::Message::CellClicked(self.cursor)),
+            Command::Cancel => Some(crate::Message::ClearSelection),
+            Command::EndTurn => Some(crate::Message::SendAction(Action::EndTurn)),
+            Command::AiMove => Some(crate::Message::RequestAiMove),
+            Command::Hint => Some(crate::Message::RequestHint),
+            Command::CycleUnits => Some(crate::Message::CycleUnits),
+            Command::Help => Some(crate::Message::ToggleHelp),
+        }
+    }
 }
@@ -0,0 +1,302 @@
+//! Atalhos de teclado configuráveis
+//!
+//! Teclas físicas (com modificadores) viram comandos semânticos. A tabela
+//! parte de um preset embutido e pode ser estendida no `config.toml`:
+//!
+//! ```toml
+//! [keys]
+//! preset = "vim"
+//!
+//! [keys.bindings]
+//! "ctrl+e" = "end_turn"
+//! "f1" = "help"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{bail, Result};
+use iced::keyboard::{Key, Modifiers};
+use serde::{Deserialize, Serialize};
+
+/// Ação do jogador, independente da tecla usada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Command {
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    Select,
+    Cancel,
+    EndTurn,
+    AiMove,
+    Hint,
+    CycleUnits,
+    Help,
+}
+
+impl Command {
+    /// Descrição exibida na ajuda
+    pub fn label(self) -> &'static str {
+        match self {
+            Command::CursorUp => "Cursor para cima",
+            Command::CursorDown => "Cursor para baixo",
+            Command::CursorLeft => "Cursor para a esquerda",
+            Command::CursorRight => "Cursor para a direita",
+            Command::Select => "Selecionar / confirmar",
+            Command::Cancel => "Cancelar seleção",
+            Command::EndTurn => "Finalizar turno",
+            Command::AiMove => "Jogada da IA",
+            Command::Hint => "Dica",
+            Command::CycleUnits => "Próxima unidade",
+            Command::Help => "Mostrar/ocultar ajuda",
+        }
+    }
+}
+
+/// Conjuntos de teclas embutidos
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyPreset {
+    /// W/A/S/D, Enter e Escape
+    #[default]
+    Wasd,
+    /// Setas, Enter e Escape
+    Arrows,
+    /// h/j/k/l, espaço e Escape
+    Vim,
+}
+
+impl KeyPreset {
+    fn bindings(self) -> Vec<(&'static str, Command)> {
+        let mut bindings = match self {
+            KeyPreset::Wasd => vec![
+                ("w", Command::CursorUp),
+                ("s", Command::CursorDown),
+                ("a", Command::CursorLeft),
+                ("d", Command::CursorRight),
+                ("enter", Command::Select),
+            ],
+            KeyPreset::Arrows => vec![
+                ("arrowup", Command::CursorUp),
+                ("arrowdown", Command::CursorDown),
+                ("arrowleft", Command::CursorLeft),
+                ("arrowright", Command::CursorRight),
+                ("enter", Command::Select),
+            ],
+            KeyPreset::Vim => vec![
+                ("k", Command::CursorUp),
+                ("j", Command::CursorDown),
+                ("h", Command::CursorLeft),
+                ("l", Command::CursorRight),
+                ("space", Command::Select),
+                ("enter", Command::Select),
+            ],
+        };
+
+        // Comuns a todos os presets
+        bindings.extend([
+            ("escape", Command::Cancel),
+            ("e", Command::EndTurn),
+            ("i", Command::AiMove),
+            ("t", Command::Hint),
+            ("tab", Command::CycleUnits),
+            ("?", Command::Help),
+        ]);
+        bindings
+    }
+}
+
+/// Seção `[keys]` do arquivo de configuração
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyConfig {
+    /// Preset de partida
+    pub preset: KeyPreset,
+    /// Atalhos extras ou substitutos, no formato `"ctrl+shift+x" = "comando"`
+    pub bindings: BTreeMap<String, Command>,
+}
+
+/// Tecla com modificadores, como "ctrl+e" ou "arrowup"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord {
+    /// Nome da tecla em minúsculas
+    pub key: String,
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl KeyChord {
+    /// Lê o formato do arquivo de configuração
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut chord = KeyChord {
+            key: String::new(),
+            shift: false,
+            ctrl: false,
+            alt: false,
+        };
+
+        // "+" sozinho (ou no fim, como em "shift++") é a própria tecla
+        let (modifiers, key) = match text.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None if text == "+" => ("", "+"),
+            None => text.rsplit_once('+').unwrap_or(("", text)),
+        };
+
+        for modifier in modifiers.split('+').filter(|part| !part.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "shift" => chord.shift = true,
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" => chord.alt = true,
+                other => bail!("Modificador desconhecido \"{}\" em \"{}\"", other, text),
+            }
+        }
+
+        if key.is_empty() {
+            bail!("Atalho sem tecla: \"{}\"", text);
+        }
+        chord.key = key.to_lowercase();
+
+        Ok(chord)
+    }
+
+    /// Tecla pressionada, como vem do iced
+    pub fn from_key(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        let name = match key {
+            Key::Character(c) => c.to_lowercase(),
+            Key::Named(named) => format!("{:?}", named).to_lowercase(),
+            Key::Unidentified => return None,
+        };
+
+        Some(KeyChord {
+            key: name,
+            shift: modifiers.shift(),
+            ctrl: modifiers.control(),
+            alt: modifiers.alt(),
+        })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Tabela de atalhos efetiva
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyChord, Command)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&KeyConfig::default()).expect("presets embutidos são válidos")
+    }
+}
+
+impl Keymap {
+    /// Preset escolhido, com os atalhos do usuário por cima
+    pub fn from_config(config: &KeyConfig) -> Result<Self> {
+        let mut keymap = Keymap { bindings: Vec::new() };
+
+        for (chord, command) in config.preset.bindings() {
+            keymap.bind(KeyChord::parse(chord)?, command);
+        }
+        for (chord, command) in &config.bindings {
+            keymap.bind(KeyChord::parse(chord)?, *command);
+        }
+
+        Ok(keymap)
+    }
+
+    fn bind(&mut self, chord: KeyChord, command: Command) {
+        self.bindings.retain(|(existing, _)| *existing != chord);
+        self.bindings.push((chord, command));
+    }
+
+    /// Comando da tecla pressionada
+    ///
+    /// Sem atalho exato, tenta sem Shift: "?" chega como Shift+/ em muitos
+    /// layouts, e Shift+Enter continua selecionando (forçando ataque). Sem
+    /// Ctrl só vale para selecionar, para Ctrl+Enter forçar movimento sem
+    /// que outros atalhos com Ctrl caiam na tecla pura.
+    pub fn command_for(&self, key: &Key, modifiers: Modifiers) -> Option<Command> {
+        let chord = KeyChord::from_key(key, modifiers)?;
+        let unshifted = KeyChord { shift: false, ..chord.clone() };
+        let bare = KeyChord { ctrl: false, ..unshifted.clone() };
+
+        self.lookup(&chord)
+            .or_else(|| self.lookup(&unshifted))
+            .or_else(|| self.lookup(&bare).filter(|command| *command == Command::Select))
+    }
+
+    fn lookup(&self, chord: &KeyChord) -> Option<Command> {
+        self.bindings
+            .iter()
+            .find(|(existing, _)| existing == chord)
+            .map(|(_, command)| *command)
+    }
+
+    /// Atalhos na ordem em que foram definidos
+    pub fn bindings(&self) -> impl Iterator<Item = &(KeyChord, Command)> {
+        self.bindings.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::keyboard::key::Named;
+
+    fn key(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn test_keymap_presets_and_overrides() {
+        let config: KeyConfig = toml::from_str(
+            "preset = \"vim\"\n[bindings]\n\"ctrl+e\" = \"end_turn\"\n\"e\" = \"hint\"",
+        )
+        .unwrap();
+        assert_eq!(config.preset, KeyPreset::Vim);
+        let keymap = Keymap::from_config(&config).unwrap();
+
+        assert_eq!(keymap.command_for(&key("k"), Modifiers::empty()), Some(Command::CursorUp));
+        assert_eq!(keymap.command_for(&key("e"), Modifiers::CTRL), Some(Command::EndTurn));
+        // O atalho do usuário substitui o do preset
+        assert_eq!(keymap.command_for(&key("e"), Modifiers::empty()), Some(Command::Hint));
+        // "?" costuma chegar com Shift
+        assert_eq!(keymap.command_for(&key("?"), Modifiers::SHIFT), Some(Command::Help));
+        assert_eq!(keymap.command_for(&Key::Named(Named::ArrowUp), Modifiers::empty()), None);
+
+        assert!(Keymap::from_config(&KeyConfig {
+            bindings: [("super+x".to_string(), Command::Hint)].into(),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_ctrl_only_falls_back_to_select() {
+        let keymap = Keymap::default();
+
+        let enter = Key::Named(Named::Enter);
+        assert_eq!(keymap.command_for(&enter, Modifiers::CTRL), Some(Command::Select));
+        assert_eq!(keymap.command_for(&enter, Modifiers::SHIFT), Some(Command::Select));
+        // Ctrl+E e Ctrl+W sem atalho próprio não finalizam o turno nem movem o cursor
+        assert_eq!(keymap.command_for(&key("e"), Modifiers::CTRL), None);
+        assert_eq!(keymap.command_for(&key("w"), Modifiers::CTRL), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Tela de lobby: lista, filtra, entra e cria partidas
+
+use iced::{
+    widget::{button, column, container, row, scrollable, text, text_input},
+    Alignment, Element, Length,
+};
+
+use crate::api::MatchSummary;
+use crate::Message;
+
+/// Estado da tela de lobby
+#[derive(Debug, Clone, Default)]
+pub struct Lobby {
+    /// Partidas retornadas pelo último `GET /matches`
+    pub matches: Vec<MatchSummary>,
+    /// Texto de filtro (ID da partida ou de um jogador)
+    pub filter: String,
+    /// Oponente para novas partidas (ID de jogador ou "ai")
+    pub opponent: String,
+    /// Segundo jogador de uma partida hot-seat
+    pub second_player: String,
+    /// As duas IAs de uma partida assistida
+    pub watch_players: [String; 2],
+    /// Há uma listagem em andamento
+    pub loading: bool,
+}
+
+impl Lobby {
+    pub fn new(default_opponent: String) -> Self {
+        Self {
+            opponent: default_opponent,
+            second_player: "player2".to_string(),
+            watch_players: ["ai-1".to_string(), "ai-2".to_string()],
+            ..Self::default()
+        }
+    }
+
+    /// Partidas que casam com o filtro atual
+    pub fn filtered(&self) -> impl Iterator<Item = &MatchSummary> {
+        let filter = self.filter.trim().to_lowercase();
+
+        self.matches.iter().filter(move |summary| {
+            filter.is_empty()
+                || summary.id.to_lowercase().contains(&filter)
+                || summary
+                    .players
+                    .iter()
+                    .any(|player| player.to_lowercase().contains(&filter))
+        })
+    }
+
+    /// Renderiza a lista de partidas e o formulário de criação
+    pub fn view(&self) -> Element<'_, Message> {
+        let toolbar = row![
+            text_input("Filtrar por partida ou jogador", &self.filter)
+                .on_input(Message::LobbyFilterChanged)
+                .width(300),
+            button(if self.loading { "Atualizando..." } else { "Atualizar" })
+                .on_press_maybe((!self.loading).then_some(Message::RefreshLobby)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let rows: Vec<Element<Message>> = self
+            .filtered()
+            .map(|summary| {
+                row![
+                    text(&summary.id).width(Length::FillPortion(3)),
+                    text(summary.players.join(" vs ")).width(Length::FillPortion(3)),
+                    text(format!("{:?}", summary.phase)).width(Length::FillPortion(2)),
+                    text(format!("Turno {}", summary.turn_count)).width(Length::FillPortion(1)),
+                    button("Entrar").on_press(Message::JoinMatch(summary.id.clone())),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into()
+            })
+            .collect();
+
+        let list: Element<Message> = if rows.is_empty() {
+            text(if self.loading {
+                "Carregando partidas..."
+            } else {
+                "Nenhuma partida encontrada"
+            })
+            .into()
+        } else {
+            scrollable(column(rows).spacing(5)).height(300).into()
+        };
+
+        let create = row![
+            text("Nova partida contra:"),
+            text_input("ID do oponente ou \"ai\"", &self.opponent)
+                .on_input(Message::LobbyOpponentChanged)
+                .width(200),
+            button("Criar").on_press_maybe(
+                (!self.opponent.trim().is_empty()).then_some(Message::CreateMatch)
+            ),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let hot_seat = row![
+            text("Hot-seat local contra:"),
+            text_input("Nome do segundo jogador", &self.second_player)
+                .on_input(Message::LobbySecondPlayerChanged)
+                .width(200),
+            button("Jogar local").on_press_maybe(
+                (!self.second_player.trim().is_empty()).then_some(Message::StartHotSeat)
+            ),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let watch = row![
+            text("Assistir IA vs IA:"),
+            text_input("Primeira IA", &self.watch_players[0])
+                .on_input(|value| Message::LobbyWatchPlayerChanged(0, value))
+                .width(120),
+            text_input("Segunda IA", &self.watch_players[1])
+                .on_input(|value| Message::LobbyWatchPlayerChanged(1, value))
+                .width(120),
+            button("Assistir").on_press_maybe(
+                self.watch_players
+                    .iter()
+                    .all(|player| !player.trim().is_empty())
+                    .then_some(Message::StartSpectator)
+            ),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        container(column![toolbar, list, create, hot_seat, watch].spacing(15))
+            .width(Length::Fill)
+            .padding(10)
+            .into()
+    }
+}
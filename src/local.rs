@@ -0,0 +1,184 @@
+//! Backend em processo: aplica `tatic_lib::Action`s num `GameState` local
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use futures::future::{self, BoxFuture, FutureExt};
+use iced::Subscription;
+use tatic_lib::{Action, GameState};
+use tracing::info;
+
+use crate::ai::{self, AiLimits};
+use crate::api::MatchSummary;
+use crate::backend::GameBackend;
+use crate::net::NetError;
+use crate::Message;
+
+/// Partidas mantidas em memória, sem servidor
+#[derive(Debug, Clone)]
+pub struct LocalBackend {
+    matches: Arc<Mutex<LocalMatches>>,
+    /// Limites da IA de `get_ai_action`
+    limits: AiLimits,
+}
+
+#[derive(Debug, Default)]
+struct LocalMatches {
+    next_id: u64,
+    matches: BTreeMap<String, LocalMatch>,
+}
+
+#[derive(Debug)]
+struct LocalMatch {
+    players: Vec<String>,
+    state: GameState,
+}
+
+impl LocalBackend {
+    pub fn new(limits: AiLimits) -> Self {
+        Self {
+            matches: Arc::new(Mutex::new(LocalMatches::default())),
+            limits,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LocalMatches> {
+        // Uma thread que entrou em pânico não deixa o estado pela metade:
+        // toda ação é aplicada numa cópia antes de substituir o original
+        self.matches.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Cria uma partida local e devolve seu ID
+    pub fn create(&self, player1: &str, player2: &str) -> String {
+        let mut inner = self.lock();
+        inner.next_id += 1;
+        let match_id = format!("local-{}", inner.next_id);
+
+        info!("🆕 Partida local {}: {} vs {}", match_id, player1, player2);
+        inner.matches.insert(
+            match_id.clone(),
+            LocalMatch {
+                players: vec![player1.to_string(), player2.to_string()],
+                state: GameState::new(player1, player2),
+            },
+        );
+
+        match_id
+    }
+
+    /// Estado atual da partida
+    pub fn state(&self, match_id: &str) -> Result<GameState, NetError> {
+        self.lock()
+            .matches
+            .get(match_id)
+            .map(|local| local.state.clone())
+            .ok_or_else(|| not_found(match_id))
+    }
+
+    /// Valida e aplica a ação de `player_id`, devolvendo o novo estado
+    pub fn apply(&self, match_id: &str, player_id: &str, action: Action) -> Result<GameState, NetError> {
+        let mut inner = self.lock();
+        let local = inner.matches.get_mut(match_id).ok_or_else(|| not_found(match_id))?;
+
+        if local.state.turn != player_id {
+            return Err(NetError::Rejected(format!("Não é o turno de {}", player_id)));
+        }
+
+        let mut next = local.state.clone();
+        next.apply_action(player_id, action)
+            .map_err(|e| NetError::Rejected(e.to_string()))?;
+        local.state = next.clone();
+
+        Ok(next)
+    }
+}
+
+impl GameBackend for LocalBackend {
+    fn list_matches(&self) -> BoxFuture<'static, Result<Vec<MatchSummary>, NetError>> {
+        let summaries = self
+            .lock()
+            .matches
+            .iter()
+            .map(|(id, local)| MatchSummary {
+                id: id.clone(),
+                players: local.players.clone(),
+                phase: local.state.phase.clone(),
+                turn_count: local.state.turn_count as u32,
+            })
+            .collect();
+
+        future::ready(Ok(summaries)).boxed()
+    }
+
+    fn create_match(
+        &self,
+        player1: String,
+        player2: String,
+    ) -> BoxFuture<'static, Result<String, NetError>> {
+        future::ready(Ok(self.create(&player1, &player2))).boxed()
+    }
+
+    fn join_match(
+        &self,
+        match_id: String,
+        _player_id: String,
+    ) -> BoxFuture<'static, Result<String, NetError>> {
+        let result = self.state(&match_id).map(|_| match_id);
+        future::ready(result).boxed()
+    }
+
+    fn get_state(&self, match_id: String) -> BoxFuture<'static, Result<GameState, NetError>> {
+        future::ready(self.state(&match_id)).boxed()
+    }
+
+    fn send_action(
+        &self,
+        match_id: String,
+        player_id: String,
+        action: Action,
+    ) -> BoxFuture<'static, Result<GameState, NetError>> {
+        future::ready(self.apply(&match_id, &player_id, action)).boxed()
+    }
+
+    fn get_ai_action(
+        &self,
+        match_id: String,
+        ai_player: String,
+    ) -> BoxFuture<'static, Result<Action, NetError>> {
+        match self.state(&match_id) {
+            Ok(state) => ai::think(state, ai_player, self.limits).boxed(),
+            Err(e) => future::ready(Err(e)).boxed(),
+        }
+    }
+
+    /// Só este processo altera as partidas locais, e cada ação já devolve o
+    /// novo estado: não há o que assinar
+    fn subscribe(&self, _match_id: &str) -> Subscription<Message> {
+        Subscription::none()
+    }
+}
+
+fn not_found(match_id: &str) -> NetError {
+    NetError::Rejected(format!("Partida {} não encontrada", match_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_local_backend_applies_actions() {
+        let backend = LocalBackend::new(Config::default().ai_limits());
+        let match_id = backend.create("player1", "player2");
+
+        let state = backend.state(&match_id).unwrap();
+        assert_eq!(state.turn, "player1");
+
+        // Fora do turno é rejeitado
+        assert!(backend.apply(&match_id, "player2", Action::EndTurn).is_err());
+
+        let state = backend.apply(&match_id, "player1", Action::EndTurn).unwrap();
+        assert_eq!(state.turn, "player2");
+    }
+}
@@ -6,26 +6,76 @@
 //! - Comunica com servidor via REST/WebSocket
 //! - Loga todas as ações detalhadamente
 
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use iced::{
-    widget::{button, column, container, row, scrollable, text},
-    Alignment, Element, Length, Task, Theme,
+    event, keyboard, task,
+    widget::{button, column, container, opaque, pick_list, row, scrollable, stack, text},
+    Alignment, Element, Event, Length, Subscription, Task, Theme,
 };
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
+mod ai;
+mod animation;
+mod api;
+mod auth;
+mod backend;
+mod config;
+mod diff;
+mod hotseat;
 mod input;
+mod keymap;
+mod lobby;
+mod local;
 mod net;
 mod renderer;
+mod rules;
+mod spectator;
+mod theme;
 mod ui;
+mod ws;
 
 use tatic_lib::{Action, Coord, GameState};
-use net::NetworkClient;
-use renderer::BoardRenderer;
+use ai::AiSource;
+use animation::Animation;
+use api::{MatchSummary, ServerEvent};
+use auth::{Login, Session, TokenStore};
+use backend::GameBackend;
+use clap::Parser;
+use config::{Cli, Config};
+use diff::BoardChange;
+use hotseat::HotSeat;
+use input::InputState;
+use keymap::Keymap;
+use lobby::Lobby;
+use local::LocalBackend;
+use net::{NetError, NetworkClient};
+use renderer::{BoardCache, BoardRenderer, Highlights};
+use spectator::Spectator;
+use theme::{OwnerColors, Palette, PaletteName};
 
 /// Estado principal da aplicação
 #[derive(Debug)]
 pub struct RpgAsciiClient {
-    /// Cliente de rede
+    /// Configuração efetiva (flags, ambiente, arquivo e padrões)
+    config: Config,
+    /// Cliente de rede (login e token)
     network: NetworkClient,
+    /// Fonte das partidas: servidor ou engine local
+    backend: Arc<dyn GameBackend>,
+    /// Engine local (modo offline e hot-seat)
+    local: LocalBackend,
+    /// Partida hot-seat em andamento
+    hot_seat: Option<HotSeat>,
+    /// Partida IA vs IA assistida
+    spectator: Option<Spectator>,
+    /// Quem joga pela IA nesta partida
+    ai_source: AiSource,
+    /// Joga os turnos da IA automaticamente
+    auto_play: bool,
+    /// Há uma jogada da IA agendada ou em andamento
+    ai_busy: bool,
     /// Estado atual do jogo
     game_state: Option<GameState>,
     /// ID da partida atual
@@ -34,12 +84,52 @@ pub struct RpgAsciiClient {
     player_id: String,
     /// Coordenada selecionada (para movimento/ataque)
     selected_coord: Option<Coord>,
+    /// Destinos da unidade selecionada, recalculados só quando a seleção ou
+    /// o estado mudam
+    targets: Option<(Coord, rules::Targets)>,
+    /// Origem e destino da jogada sugerida pela IA
+    hint: Option<(Coord, Coord)>,
+    /// Desenho do tabuleiro reaproveitado entre renderizações
+    board_cache: BoardCache,
+    /// Cores da interface e do tabuleiro
+    palette: Palette,
+    /// Cor de cada dono de unidade no estado atual
+    owner_colors: OwnerColors,
+    /// Transição do tabuleiro em andamento
+    animation: Option<Animation>,
+    /// Origem e destino da última ação vista no tabuleiro
+    last_move: Option<(Coord, Coord)>,
+    /// Cursor e modificadores do teclado
+    input: InputState,
+    /// Atalhos de teclado configurados
+    keymap: Keymap,
+    /// Painel de atalhos visível
+    show_help: bool,
+    /// Ação recusada pela validação local (para "Enviar mesmo assim")
+    blocked_action: Option<Action>,
     /// Log de mensagens
     message_log: Vec<String>,
     /// Estado da conexão
     connection_status: ConnectionStatus,
     /// Modo de input
     input_mode: InputMode,
+    /// Tela exibida
+    screen: Screen,
+    /// Estado do lobby de partidas
+    lobby: Lobby,
+    /// Estado da tela de login
+    login: Login,
+    /// Sessão (token) salva em disco
+    token_store: TokenStore,
+    /// Ação em andamento no servidor (para permitir cancelar)
+    pending_action: Option<task::Handle>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Screen {
+    Login,
+    Lobby,
+    Game,
 }
 
 #[derive(Debug, Clone)]
@@ -47,9 +137,25 @@ enum ConnectionStatus {
     Disconnected,
     Connecting,
     Connected,
+    Reconnecting(u32),
     Error(String),
 }
 
+impl ConnectionStatus {
+    /// Texto exibido na barra de status
+    fn label(&self) -> String {
+        match self {
+            ConnectionStatus::Disconnected => "Desconectado".to_string(),
+            ConnectionStatus::Connecting => "Conectando...".to_string(),
+            ConnectionStatus::Connected => "Conectado".to_string(),
+            ConnectionStatus::Reconnecting(attempt) => {
+                format!("Reconectando (tentativa {})", attempt)
+            }
+            ConnectionStatus::Error(e) => format!("Erro: {}", e),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum InputMode {
     SelectUnit,
@@ -62,47 +168,152 @@ enum InputMode {
 pub enum Message {
     // Conexão
     Connect,
-    ConnectionResult(Result<String, String>),
+    ConnectionResult(Result<String, NetError>),
+    
+    // Autenticação
+    LoginPlayerChanged(String),
+    LoginPasswordChanged(String),
+    SubmitLogin,
+    LoginResult(Result<String, NetError>),
+    SessionExpired,
+    Logout,
+    
+    // Lobby
+    OpenLobby,
+    RefreshLobby,
+    LobbyLoaded(Result<Vec<MatchSummary>, NetError>),
+    LobbyFilterChanged(String),
+    LobbyOpponentChanged(String),
+    LobbySecondPlayerChanged(String),
+    JoinMatch(String),
+    CreateMatch,
+    
+    // Hot-seat
+    StartHotSeat,
+    HandoffReady,
+    
+    // Espectador (IA vs IA)
+    LobbyWatchPlayerChanged(usize, String),
+    StartSpectator,
+    SpectatorTogglePause,
+    SpectatorStep,
+    SpectatorFaster,
+    SpectatorSlower,
     
     // Ações do jogo
     CellClicked(Coord),
     SendAction(Action),
-    ActionResult(Result<GameState, String>),
+    ForceSendAction,
+    ActionResult(Result<GameState, NetError>),
+    CancelAction,
+    StateUpdated(GameState),
+    StateLoaded(String, Result<GameState, NetError>),
     
     // WebSocket
     WebSocketMessage(String),
+    WebSocketConnected,
+    WebSocketReconnecting(u32, String),
     
     // UI
     ClearSelection,
     RefreshState,
     
     // Navegação
-    KeyPressed(char),
+    KeyboardEvent(Event),
+    CursorMoved(Coord),
+    CycleUnits,
+    ToggleHelp,
+    ThemeSelected(PaletteName),
+    AnimationTick(Instant),
     
     // IA
+    ToggleAiSource,
     RequestAiMove,
-    AiMoveResult(Result<Action, String>),
+    RequestHint,
+    HintResult(String, Result<Action, NetError>),
+    AiTurnTick(String),
+    ToggleAutoPlay,
+    AiMoveResult(String, String, Result<Action, NetError>),
+    AiActionResult(Result<GameState, NetError>),
 }
 
 impl RpgAsciiClient {
     /// Cria nova instância do cliente
-    pub fn new() -> (Self, Task<Message>) {
-        init_logging();
+    pub fn new(config: Config) -> (Self, Task<Message>) {
+        let local = LocalBackend::new(config.ai_limits());
+        Self::from_parts(config, TokenStore::new(), local)
+    }
+
+    /// Cria o cliente com a sessão e a engine local recebidas (os testes
+    /// passam uma `TokenStore` sem arquivo)
+    fn from_parts(config: Config, token_store: TokenStore, local: LocalBackend) -> (Self, Task<Message>) {
+        init_logging(&config.log_filter);
         
         info!("🎮 Iniciando cliente do RPG ASCII Tático");
+        if config.offline {
+            info!("Modo offline | Jogador: {}", config.player_id);
+        } else {
+            info!("Servidor: {} | Jogador: {}", config.server_url, config.player_id);
+        }
+        
+        let mut network = NetworkClient::with_policy(config.server_url.clone(), config.request_policy());
+        let session = token_store.load(&config.server_url, &config.player_id);
+        if session.is_some() {
+            info!("🔑 Sessão salva encontrada para {}", config.player_id);
+        }
+        network.set_token(session.map(|session| session.token));
+        
+        let backend: Arc<dyn GameBackend> = if config.offline {
+            Arc::new(local.clone())
+        } else {
+            Arc::new(network.clone())
+        };
         
         let client = Self {
-            network: NetworkClient::new("http://localhost:3000".to_string()),
+            network,
+            backend,
+            local,
+            hot_seat: None,
+            spectator: None,
+            ai_source: if config.offline { AiSource::Local } else { config.ai_source },
+            auto_play: config.ai_auto_play,
+            ai_busy: false,
             game_state: None,
             match_id: None,
-            player_id: "player1".to_string(),
+            player_id: config.player_id.clone(),
+            lobby: Lobby::new(config.default_opponent.clone()),
+            login: Login::new(config.player_id.clone()),
+            token_store,
             selected_coord: None,
+            targets: None,
+            hint: None,
+            board_cache: BoardCache::new(),
+            palette: config.theme.palette(),
+            owner_colors: OwnerColors::default(),
+            animation: None,
+            last_move: None,
+            input: InputState::new(),
+            keymap: config.keymap().unwrap_or_else(|e| {
+                warn!("⚠️ {:#}, usando atalhos padrão", e);
+                Keymap::default()
+            }),
+            show_help: false,
+            blocked_action: None,
             message_log: vec!["Bem-vindo ao RPG ASCII Tático!".to_string()],
             connection_status: ConnectionStatus::Disconnected,
             input_mode: InputMode::SelectUnit,
+            screen: Screen::Login,
+            pending_action: None,
+            // Por último: os campos acima ainda leem a configuração
+            config,
         };
         
-        (client, Task::none())
+        if !client.config.offline && client.network.token().is_none() {
+            return (client, Task::none());
+        }
+        
+        // Já autenticado (ou offline): abre o lobby listando as partidas
+        (client, Task::perform(async {}, |_| Message::Connect))
     }
 
     /// Título da janela
@@ -112,17 +323,263 @@ impl RpgAsciiClient {
 
     /// Processa mensagens e retorna tasks
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle_message(message);
+        self.sync_targets();
+        task
+    }
+
+    fn handle_message(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::Connect => {
+                // Já existe uma partida: volta para ela em vez de pegar outra
+                if let Some(match_id) = &self.match_id {
+                    info!("🔌 Reconectando à partida {}", match_id);
+                    self.connection_status = ConnectionStatus::Connecting;
+                    self.add_log(format!("Reconectando à partida {}...", match_id));
+                    self.screen = Screen::Game;
+                    return Task::perform(async {}, |_| Message::RefreshState);
+                }
+
                 info!("🔌 Tentando conectar ao servidor...");
                 self.connection_status = ConnectionStatus::Connecting;
-                self.add_log("Conectando ao servidor...".to_string());
+                Task::perform(async {}, |_| Message::OpenLobby)
+            }
+            
+            Message::LoginPlayerChanged(player_id) => {
+                self.login.player_id = player_id;
+                Task::none()
+            }
+            
+            Message::LoginPasswordChanged(password) => {
+                self.login.password = password;
+                Task::none()
+            }
+            
+            Message::SubmitLogin => {
+                let player_id = self.login.player_id.trim().to_string();
+                let password = self.login.password.clone();
+                self.login.busy = true;
                 
                 let network = self.network.clone();
                 Task::perform(
                     async move {
-                        network.connect().await
+                        network.login(&player_id, &password).await
                     },
+                    Message::LoginResult,
+                )
+            }
+            
+            Message::LoginResult(result) => {
+                self.login.busy = false;
+                match result {
+                    Ok(token) => {
+                        self.player_id = self.login.player_id.trim().to_string();
+                        self.login.password.clear();
+                        info!("🔑 Autenticado como {}", self.player_id);
+                        self.add_log(format!("Autenticado como {}", self.player_id));
+                        
+                        let session = Session {
+                            server_url: self.network.base_url().to_string(),
+                            player_id: self.player_id.clone(),
+                            token: token.clone(),
+                        };
+                        if let Err(e) = self.token_store.save(&session) {
+                            warn!("⚠️ Não foi possível salvar a sessão: {}", e);
+                        }
+                        self.network.set_token(Some(token));
+                        self.backend = self.default_backend();
+                        
+                        Task::perform(async {}, |_| Message::Connect)
+                    }
+                    Err(NetError::Unauthorized) | Err(NetError::Rejected(_)) => {
+                        warn!("⚠️ Login recusado para {}", self.login.player_id);
+                        self.add_log("Login recusado: jogador ou senha inválidos".to_string());
+                        Task::none()
+                    }
+                    Err(e) => {
+                        self.handle_net_error(e);
+                        Task::none()
+                    }
+                }
+            }
+            
+            Message::SessionExpired => {
+                self.expire_session();
+                Task::none()
+            }
+            
+            Message::Logout => {
+                info!("🔑 Logout de {}", self.player_id);
+                self.token_store.clear();
+                self.network.set_token(None);
+                self.backend = self.default_backend();
+                self.hot_seat = None;
+                self.spectator = None;
+                self.leave_match();
+                self.screen = Screen::Login;
+                self.connection_status = ConnectionStatus::Disconnected;
+                self.add_log("Sessão encerrada".to_string());
+                Task::none()
+            }
+            
+            Message::OpenLobby => {
+                if let Some(hot_seat) = self.hot_seat.take() {
+                    info!("🪑 Saindo do hot-seat");
+                    self.player_id = hot_seat.home_player;
+                    self.backend = self.default_backend();
+                    self.selected_coord = None;
+                    self.input_mode = InputMode::SelectUnit;
+                }
+                if self.spectator.take().is_some() {
+                    info!("👀 Saindo do modo espectador");
+                    self.backend = self.default_backend();
+                }
+                // Sem partida, a assinatura de atualizações ao vivo é encerrada
+                self.leave_match();
+                self.screen = Screen::Lobby;
+                Task::perform(async {}, |_| Message::RefreshLobby)
+            }
+            
+            Message::RefreshLobby => {
+                info!("📋 Listando partidas...");
+                self.lobby.loading = true;
+                
+                Task::perform(self.backend.list_matches(), Message::LobbyLoaded)
+            }
+            
+            Message::LobbyLoaded(result) => {
+                self.lobby.loading = false;
+                match result {
+                    Ok(matches) => {
+                        info!("📋 {} partidas encontradas", matches.len());
+                        if let ConnectionStatus::Connecting | ConnectionStatus::Error(_) = self.connection_status {
+                            self.connection_status = ConnectionStatus::Connected;
+                        }
+                        self.lobby.matches = matches;
+                    }
+                    Err(e) => {
+                        self.handle_net_error(e);
+                    }
+                }
+                Task::none()
+            }
+            
+            Message::LobbyFilterChanged(filter) => {
+                self.lobby.filter = filter;
+                Task::none()
+            }
+            
+            Message::LobbyOpponentChanged(opponent) => {
+                self.lobby.opponent = opponent;
+                Task::none()
+            }
+            
+            Message::LobbySecondPlayerChanged(player) => {
+                self.lobby.second_player = player;
+                Task::none()
+            }
+            
+            Message::StartHotSeat => {
+                let player1 = self.player_id.clone();
+                let player2 = self.lobby.second_player.trim().to_string();
+                if player1 == player2 {
+                    self.add_log("Os dois jogadores precisam de nomes diferentes".to_string());
+                    return Task::none();
+                }
+                
+                info!("🪑 Hot-seat local: {} vs {}", player1, player2);
+                let match_id = self.local.create(&player1, &player2);
+                self.hot_seat = Some(HotSeat::new(player1.clone(), player2, player1));
+                self.backend = Arc::new(self.local.clone());
+                
+                Task::perform(async move { Ok(match_id) }, Message::ConnectionResult)
+            }
+            
+            Message::LobbyWatchPlayerChanged(index, value) => {
+                if let Some(player) = self.lobby.watch_players.get_mut(index) {
+                    *player = value;
+                }
+                Task::none()
+            }
+            
+            Message::StartSpectator => {
+                let [player1, player2] = self.lobby.watch_players.clone().map(|player| player.trim().to_string());
+                if player1 == player2 {
+                    self.add_log("As duas IAs precisam de nomes diferentes".to_string());
+                    return Task::none();
+                }
+                
+                info!("👀 Assistindo IA vs IA: {} vs {}", player1, player2);
+                self.add_log(format!("Criando partida {} vs {}...", player1, player2));
+                self.spectator = Some(Spectator::new(player1.clone(), player2.clone()));
+                // O cliente joga pelas duas IAs, o que só a engine local aceita
+                self.backend = Arc::new(self.local.clone());
+                
+                Task::perform(
+                    self.backend.create_match(player1, player2),
+                    Message::ConnectionResult,
+                )
+            }
+            
+            Message::SpectatorTogglePause => {
+                let Some(spectator) = &mut self.spectator else {
+                    return Task::none();
+                };
+                spectator.paused = !spectator.paused;
+                let paused = spectator.paused;
+                self.add_log(if paused { "Reprodução pausada" } else { "Reprodução retomada" }.to_string());
+                self.schedule_ai_turn()
+            }
+            
+            Message::SpectatorStep => {
+                if self.ai_busy || !self.spectator.as_ref().is_some_and(|spectator| spectator.paused) {
+                    return Task::none();
+                }
+                self.request_ai_move()
+            }
+            
+            Message::SpectatorFaster => {
+                if let Some(spectator) = &mut self.spectator {
+                    spectator.faster();
+                    info!("⏩ Velocidade {}x", spectator.speed);
+                }
+                Task::none()
+            }
+            
+            Message::SpectatorSlower => {
+                if let Some(spectator) = &mut self.spectator {
+                    spectator.slower();
+                    info!("⏪ Velocidade {}x", spectator.speed);
+                }
+                Task::none()
+            }
+            
+            Message::HandoffReady => {
+                let player = self
+                    .hot_seat
+                    .as_mut()
+                    .and_then(|hot_seat| hot_seat.handoff_to.take());
+                if let Some(player) = player {
+                    self.add_log(format!("Vez de {}", player));
+                }
+                Task::none()
+            }
+            
+            Message::JoinMatch(match_id) => {
+                info!("🎯 Entrando na partida {}", match_id);
+                Task::perform(
+                    self.backend.join_match(match_id, self.player_id.clone()),
+                    Message::ConnectionResult,
+                )
+            }
+            
+            Message::CreateMatch => {
+                let opponent = self.lobby.opponent.trim().to_string();
+                info!("🆕 Criando partida {} vs {}", self.player_id, opponent);
+                self.add_log(format!("Criando partida contra {}...", opponent));
+                
+                Task::perform(
+                    self.backend.create_match(self.player_id.clone(), opponent),
                     Message::ConnectionResult,
                 )
             }
@@ -133,6 +590,13 @@ impl RpgAsciiClient {
                         info!("✅ Conectado! Match ID: {}", match_id);
                         self.connection_status = ConnectionStatus::Connected;
                         self.match_id = Some(match_id.clone());
+                        self.game_state = None;
+                        self.last_move = None;
+                        self.animation = None;
+                        self.selected_coord = None;
+                        self.input_mode = InputMode::SelectUnit;
+                        self.screen = Screen::Game;
+                        self.ai_source = self.default_ai_source();
                         self.add_log(format!("Conectado à partida: {}", match_id));
                         
                         // Busca estado inicial
@@ -140,7 +604,11 @@ impl RpgAsciiClient {
                     }
                     Err(e) => {
                         error!("❌ Erro ao conectar: {}", e);
-                        self.connection_status = ConnectionStatus::Error(e.clone());
+                        // A partida IA vs IA não chegou a existir
+                        if self.spectator.take().is_some() {
+                            self.backend = self.default_backend();
+                        }
+                        self.connection_status = ConnectionStatus::Error(e.to_string());
                         self.add_log(format!("Erro: {}", e));
                         Task::none()
                     }
@@ -149,6 +617,11 @@ impl RpgAsciiClient {
             
             Message::CellClicked(coord) => {
                 info!("🖱️ Célula clicada: {}", coord);
+                self.input.move_cursor_to(coord);
+                
+                if self.handoff_pending() {
+                    return Task::none();
+                }
                 
                 match self.input_mode {
                     InputMode::SelectUnit => {
@@ -173,9 +646,33 @@ impl RpgAsciiClient {
                     }
                     
                     InputMode::SelectTarget => {
+                        // Clicar na própria seleção desfaz; em outra unidade própria, troca
+                        if self.selected_coord == Some(coord) {
+                            self.selected_coord = None;
+                            self.input_mode = InputMode::SelectUnit;
+                            self.add_log("Seleção desfeita".to_string());
+                            return Task::none();
+                        }
+                        let is_own_unit = self.game_state.as_ref().is_some_and(|state| {
+                            rules::owner_at(state, &coord) == Some(self.player_id.as_str())
+                        });
+                        if is_own_unit {
+                            self.selected_coord = Some(coord);
+                            self.add_log(format!("Unidade selecionada em {}", coord));
+                            info!("✅ Seleção trocada para {}", coord);
+                            return Task::none();
+                        }
+                        
                         if let Some(from) = self.selected_coord {
-                            // Determina se é movimento ou ataque
-                            let action = if let Some(state) = &self.game_state {
+                            // Shift força ataque e Ctrl força movimento;
+                            // sem modificador, infere pelo conteúdo do alvo
+                            let action = if self.input.shift_pressed {
+                                info!("⚔️ Ataque forçado em {}", coord);
+                                Action::Attack { from, to: coord }
+                            } else if self.input.ctrl_pressed {
+                                info!("🚶 Movimento forçado para {}", coord);
+                                Action::Move { from, to: coord }
+                            } else if let Some(state) = &self.game_state {
                                 if let Some(cell) = state.board.get(&coord) {
                                     if cell.get_unit().is_some() {
                                         Action::Attack { from, to: coord }
@@ -189,6 +686,16 @@ impl RpgAsciiClient {
                                 Action::Move { from, to: coord }
                             };
                             
+                            // Fora das casas destacadas: explica e mantém a seleção
+                            // (em depuração, segue para a validação com override)
+                            if let Some(reason) = self
+                                .explain_unreachable(from, &action)
+                                .filter(|_| !self.config.debug_actions)
+                            {
+                                self.add_log(reason);
+                                return Task::none();
+                            }
+                            
                             self.input_mode = InputMode::WaitingResponse;
                             let action_clone = action.clone();
                             return Task::perform(async {}, move |_| Message::SendAction(action_clone));
@@ -204,176 +711,396 @@ impl RpgAsciiClient {
             }
             
             Message::SendAction(action) => {
-                if let Some(match_id) = &self.match_id {
-                    info!("📤 Enviando ação: {:?}", action);
-                    
-                    let log_message = format!("Enviando: {:?}", action);
-                    let network = self.network.clone();
-                    let match_id = match_id.clone();
-                    let player_id = self.player_id.clone();
-                    
-                    self.add_log(log_message);
-                    
-                    // Log detalhado ANTES do request
-                    info!(
-                        "Client -> POST /action {{player: {}, action: {:?}}}",
-                        player_id, action
-                    );
-                    
-                    Task::perform(
-                        async move {
-                            network.send_action(&match_id, &player_id, action).await
-                        },
-                        Message::ActionResult,
-                    )
-                } else {
-                    self.add_log("Não conectado!".to_string());
-                    Task::none()
+                self.blocked_action = None;
+                
+                if let Some(state) = &self.game_state {
+                    if let Err(reason) = rules::validate(state, &self.player_id, &action) {
+                        warn!("⚠️ Ação recusada localmente: {:?} ({})", action, reason);
+                        self.add_log(format!("Ação inválida: {}", reason));
+                        self.input_mode = InputMode::SelectUnit;
+                        self.selected_coord = None;
+                        if self.config.debug_actions {
+                            self.blocked_action = Some(action);
+                        }
+                        return Task::none();
+                    }
                 }
+                
+                let player_id = self.player_id.clone();
+                self.send_action_as(player_id, action, Message::ActionResult)
+            }
+            
+            Message::ForceSendAction => {
+                let Some(action) = self.blocked_action.take() else {
+                    return Task::none();
+                };
+                warn!("🐞 Enviando ação sem validação: {:?}", action);
+                let player_id = self.player_id.clone();
+                self.send_action_as(player_id, action, Message::ActionResult)
             }
             
             Message::ActionResult(result) => {
-                match result {
-                    Ok(new_state) => {
-                        info!("✅ Ação aceita pelo servidor");
-                        self.game_state = Some(new_state);
-                        self.selected_coord = None;
-                        self.input_mode = InputMode::SelectUnit;
-                        self.add_log("Ação executada com sucesso!".to_string());
-                    }
-                    Err(e) => {
-                        error!("❌ Erro na ação: {}", e);
-                        self.add_log(format!("Erro: {}", e));
-                        self.input_mode = InputMode::SelectUnit;
-                        self.selected_coord = None;
-                    }
+                self.apply_action_result(result);
+                // A ação do jogador pode ter passado o turno para a IA
+                self.schedule_ai_turn()
+            }
+            
+            Message::CancelAction => {
+                if let Some(handle) = self.pending_action.take() {
+                    info!("🛑 Ação cancelada pelo usuário");
+                    handle.abort();
+                    self.add_log("Ação cancelada".to_string());
                 }
-                Task::none()
+                if self.ai_busy {
+                    // Sem pausar, a IA voltaria a jogar logo em seguida
+                    self.pause_auto_play();
+                    self.ai_busy = false;
+                }
+                self.selected_coord = None;
+                self.input_mode = InputMode::SelectUnit;
+                
+                // O servidor pode ter aplicado a ação mesmo assim
+                Task::perform(async {}, |_| Message::RefreshState)
             }
             
             Message::RefreshState => {
-                if let Some(match_id) = &self.match_id {
-                    let network = self.network.clone();
-                    let match_id = match_id.clone();
-                    
-                    Task::perform(
-                        async move {
-                            network.get_state(&match_id).await
-                        },
-                        |result| match result {
-                            Ok(state) => Message::ActionResult(Ok(state)),
-                            Err(e) => Message::ActionResult(Err(e)),
-                        },
-                    )
+                if let Some(match_id) = self.match_id.clone() {
+                    Task::perform(self.backend.get_state(match_id.clone()), move |result| {
+                        Message::StateLoaded(match_id.clone(), result)
+                    })
                 } else {
                     Task::none()
                 }
             }
             
+            Message::StateLoaded(match_id, result) => {
+                if !self.is_current_match(&match_id) {
+                    return Task::none();
+                }
+                match result {
+                    Ok(state) => {
+                        if let ConnectionStatus::Connecting | ConnectionStatus::Error(_) = self.connection_status {
+                            self.connection_status = ConnectionStatus::Connected;
+                        }
+                        self.set_game_state(state);
+                        // Uma ação ainda em andamento continua esperando a resposta
+                        if self.pending_action.is_none() {
+                            self.selected_coord = None;
+                            self.input_mode = InputMode::SelectUnit;
+                        }
+                        self.sync_hot_seat_turn();
+                    }
+                    Err(e) => self.handle_net_error(e),
+                }
+                // Não libera `ai_busy`: uma jogada da IA pode estar em andamento
+                self.schedule_ai_turn()
+            }
+            
             Message::ClearSelection => {
                 self.selected_coord = None;
+                self.hint = None;
                 self.input_mode = InputMode::SelectUnit;
                 self.add_log("Seleção limpa".to_string());
                 Task::none()
             }
             
-            Message::KeyPressed(key) => {
-                info!("⌨️ Tecla pressionada: {}", key);
-                // Implementar controle por teclado WASD
+            Message::KeyboardEvent(event) => {
+                if self.screen != Screen::Game {
+                    return Task::none();
+                }
+                
+                // Na troca de turno do hot-seat, Enter confirma
+                if self.handoff_pending() {
+                    if let Event::Keyboard(keyboard::Event::KeyPressed {
+                        key: keyboard::Key::Named(keyboard::key::Named::Enter),
+                        ..
+                    }) = event
+                    {
+                        return self.update(Message::HandoffReady);
+                    }
+                    return Task::none();
+                }
+                
+                match self.input.handle_keyboard_event(event, &self.keymap) {
+                    Some(message) => self.update(message),
+                    None => Task::none(),
+                }
+            }
+            
+            Message::CursorMoved(coord) => {
+                debug!("⌨️ Cursor em {}", coord);
+                Task::none()
+            }
+            
+            Message::CycleUnits => {
+                let Some(state) = &self.game_state else {
+                    return Task::none();
+                };
+                
+                // Próxima unidade própria depois do cursor, linha a linha
+                let units: Vec<Coord> = rules::coords(&state.board)
+                    .filter(|coord| rules::owner_at(state, coord) == Some(self.player_id.as_str()))
+                    .collect();
+                let cursor = self.input.cursor;
+                let next = units
+                    .iter()
+                    .find(|coord| (coord.y, coord.x) > (cursor.y, cursor.x))
+                    .or_else(|| units.first());
+                
+                if let Some(&coord) = next {
+                    self.input.move_cursor_to(coord);
+                }
+                Task::none()
+            }
+            
+            Message::ToggleHelp => {
+                self.show_help = !self.show_help;
+                Task::none()
+            }
+            
+            Message::AnimationTick(now) => {
+                if let Some(animation) = &mut self.animation {
+                    if !animation.tick(now) {
+                        self.animation = None;
+                    }
+                }
+                Task::none()
+            }
+            
+            Message::ThemeSelected(name) => {
+                info!("🎨 Tema: {}", name);
+                self.config.theme = name;
+                self.palette = name.palette();
+                self.refresh_owner_colors();
+                self.board_cache.invalidate();
+                
+                if let Err(e) = self.config.save_theme() {
+                    warn!("⚠️ Tema não salvo: {:#}", e);
+                    self.add_log(format!("Tema não salvo: {}", e));
+                }
+                Task::none()
+            }
+            
+            Message::ToggleAiSource => {
+                self.ai_source = match self.ai_source {
+                    AiSource::Server => AiSource::Local,
+                    AiSource::Local => AiSource::Server,
+                };
+                self.add_log(format!("IA desta partida: {}", ai_source_label(self.ai_source)));
                 Task::none()
             }
             
             Message::RequestAiMove => {
-                if let (Some(match_id), Some(state)) = (&self.match_id, &self.game_state) {
-                    if state.turn != self.player_id {
-                        info!("🤖 Solicitando movimento da IA...");
-                        let network = self.network.clone();
-                        let match_id = match_id.clone();
-                        let ai_player = state.turn.clone();
-                        
-                        Task::perform(
-                            async move {
-                                network.get_ai_action(&match_id, &ai_player).await
-                            },
-                            Message::AiMoveResult,
-                        )
-                    } else {
-                        Task::none()
+                if self.ai_busy {
+                    self.add_log("A IA já está jogando".to_string());
+                    return Task::none();
+                }
+                // No próprio turno, a IA só sugere
+                if self.game_state.as_ref().is_some_and(|state| state.turn == self.player_id) {
+                    return Task::perform(async {}, |_| Message::RequestHint);
+                }
+                self.request_ai_move()
+            }
+            
+            Message::RequestHint => {
+                let (Some(match_id), Some(state)) = (&self.match_id, &self.game_state) else {
+                    return Task::none();
+                };
+                if state.turn != self.player_id {
+                    self.add_log("Dicas só no seu turno".to_string());
+                    return Task::none();
+                }
+                
+                info!("💡 Pedindo dica para {}", self.player_id);
+                let hint_for = match_id.clone();
+                let on_result = move |result| Message::HintResult(hint_for.clone(), result);
+                match self.ai_source {
+                    AiSource::Server => Task::perform(
+                        self.backend.get_ai_action(match_id.clone(), self.player_id.clone()),
+                        on_result,
+                    ),
+                    AiSource::Local => Task::perform(
+                        ai::think(state.clone(), self.player_id.clone(), self.config.ai_limits()),
+                        on_result,
+                    ),
+                }
+            }
+            
+            Message::HintResult(match_id, result) => {
+                if !self.is_current_match(&match_id) {
+                    return Task::none();
+                }
+                match result {
+                    Ok(Action::Move { from, to }) => {
+                        self.hint = Some((from, to));
+                        self.add_log(format!("Dica: mover de {} para {}", from, to));
                     }
-                } else {
-                    Task::none()
+                    Ok(Action::Attack { from, to }) => {
+                        self.hint = Some((from, to));
+                        self.add_log(format!("Dica: atacar {} com {}", to, from));
+                    }
+                    Ok(action) => {
+                        self.hint = None;
+                        self.add_log(format!("Dica: {:?}", action));
+                    }
+                    Err(e) => self.handle_net_error(e),
+                }
+                Task::none()
+            }
+            
+            Message::AiTurnTick(match_id) => {
+                // A espera era de outra partida: a atual agenda a própria
+                if !self.is_current_match(&match_id) {
+                    return Task::none();
                 }
+                // Pode ter sido pausado ou deixado a partida durante a espera
+                if !self.auto_play_enabled() || self.screen != Screen::Game {
+                    self.ai_busy = false;
+                    return Task::none();
+                }
+                self.request_ai_move()
             }
             
-            Message::AiMoveResult(result) => {
+            Message::ToggleAutoPlay => {
+                self.auto_play = !self.auto_play;
+                info!("🤖 Jogo automático da IA: {}", self.auto_play);
+                self.add_log(if self.auto_play {
+                    "Jogo automático da IA ligado".to_string()
+                } else {
+                    "Jogo automático da IA pausado".to_string()
+                });
+                self.schedule_ai_turn()
+            }
+            
+            Message::AiMoveResult(match_id, ai_player, result) => {
+                if !self.is_current_match(&match_id) {
+                    return Task::none();
+                }
                 match result {
                     Ok(action) => {
-                        info!("🤖 IA escolheu: {:?}", action);
-                        self.add_log(format!("IA joga: {:?}", action));
-                        let action_clone = action.clone();
-                        Task::perform(async {}, move |_| Message::SendAction(action_clone))
+                        info!("🤖 IA ({}) escolheu: {:?}", ai_player, action);
+                        self.add_log(format!("IA ({}) joga: {:?}", ai_player, action));
+                        self.send_action_as(ai_player, action, Message::AiActionResult)
                     }
                     Err(e) => {
-                        error!("❌ Erro na IA: {}", e);
-                        self.add_log(format!("Erro IA: {}", e));
+                        self.ai_busy = false;
+                        self.pause_auto_play();
+                        self.add_log("IA não conseguiu jogar, jogo automático pausado".to_string());
+                        self.handle_net_error(e);
                         Task::none()
                     }
                 }
             }
             
+            Message::AiActionResult(result) => {
+                if result.is_err() && self.auto_play_enabled() {
+                    // A IA repetiria a mesma jogada inválida para sempre
+                    self.pause_auto_play();
+                    self.add_log("Jogada da IA recusada, jogo automático pausado".to_string());
+                }
+                self.apply_action_result(result);
+                self.ai_busy = false;
+                self.schedule_ai_turn()
+            }
+            
             Message::WebSocketMessage(msg) => {
                 info!("📨 WebSocket: {}", msg);
                 // Processar mensagem do WebSocket
-                if let Ok(data) = serde_json::from_str::<serde_json::Value>(&msg) {
-                    if data["type"] == "state_update" {
-                        if let Ok(state) = serde_json::from_value::<GameState>(data["state"].clone()) {
-                            self.game_state = Some(state);
-                            self.add_log("Estado atualizado via WebSocket".to_string());
-                        }
+                match serde_json::from_str::<ServerEvent>(&msg) {
+                    Ok(ServerEvent::StateUpdate { state }) => {
+                        return self.update(Message::StateUpdated(state));
                     }
+                    Ok(ServerEvent::Unknown) => {}
+                    Err(e) => {
+                        warn!("⚠️ Mensagem WebSocket inválida: {}", e);
+                    }
+                }
+                Task::none()
+            }
+            
+            Message::StateUpdated(state) => {
+                self.set_game_state(state);
+                self.add_log("Estado atualizado ao vivo".to_string());
+                self.sync_hot_seat_turn();
+                self.schedule_ai_turn()
+            }
+            
+            Message::WebSocketConnected => {
+                if let ConnectionStatus::Reconnecting(_) = self.connection_status {
+                    info!("✅ Reconectado, ressincronizando estado");
+                    self.add_log("Reconectado! Ressincronizando tabuleiro...".to_string());
+                }
+                self.connection_status = ConnectionStatus::Connected;
+                
+                // Busca o estado completo para não perder ações feitas offline
+                Task::perform(async {}, |_| Message::RefreshState)
+            }
+            
+            Message::WebSocketReconnecting(attempt, reason) => {
+                warn!("🔌 WebSocket desconectado: {} (tentativa {})", reason, attempt);
+                if attempt == 1 {
+                    self.add_log(format!("Conexão perdida: {}", reason));
                 }
+                self.connection_status = ConnectionStatus::Reconnecting(attempt);
                 Task::none()
             }
         }
     }
 
+    /// Assinaturas ativas (atualizações ao vivo da partida atual)
+    pub fn subscription(&self) -> Subscription<Message> {
+        // Sem sessão válida o servidor recusaria o handshake
+        if self.screen == Screen::Login {
+            return Subscription::none();
+        }
+        
+        let updates = match &self.match_id {
+            Some(match_id) => self.backend.subscribe(match_id),
+            None => Subscription::none(),
+        };
+        
+        if self.screen != Screen::Game {
+            return updates;
+        }
+        
+        // Só pede quadros enquanto há algo animando
+        let frames = if self.animation.is_some() {
+            iced::time::every(Duration::from_millis(16)).map(Message::AnimationTick)
+        } else {
+            Subscription::none()
+        };
+        
+        Subscription::batch([updates, keyboard_events(), frames])
+    }
+
+    /// Tema do iced da paleta atual
+    pub fn theme(&self) -> Theme {
+        self.palette.theme.clone()
+    }
+
     /// Renderiza a interface
     pub fn view(&self) -> Element<Message> {
         let title = text("RPG ASCII Tático")
             .size(30);
 
-        let status = text(format!("Status: {:?}", self.connection_status))
-            .size(16);
+        // Offline não há sessão para encerrar, mesmo com um token salvo
+        let logout = (!self.config.offline)
+            .then(|| button("Sair").on_press_maybe(self.network.token().map(|_| Message::Logout)));
 
-        // Botões de controle
-        let controls = row![
+        let status = row![
+            text(format!("Status: {}", self.connection_status.label())).size(16),
             button("Conectar").on_press(Message::Connect),
-            button("Finalizar Turno").on_press(Message::SendAction(Action::EndTurn)),
-            button("IA Jogar").on_press(Message::RequestAiMove),
-            button("Limpar Seleção").on_press(Message::ClearSelection),
         ]
-        .spacing(10);
+        .push_maybe(logout)
+        .push(pick_list(PaletteName::ALL, Some(self.config.theme), Message::ThemeSelected))
+        .spacing(10)
+        .align_y(Alignment::Center);
 
-        // Renderiza tabuleiro
-        let board_view = if let Some(state) = &self.game_state {
-            BoardRenderer::render(&state.board, self.selected_coord)
-        } else {
-            container(text("Aguardando conexão..."))
-                .width(Length::Fill)
-                .height(400)
-                .center_x(Length::Fill)
-                .center_y(Length::Fill)
-        };
-
-        // Informações do jogo
-        let game_info = if let Some(state) = &self.game_state {
-            column![
-                text(format!("Turno: {}", state.turn)),
-                text(format!("Contador: {}", state.turn_count)),
-                text(format!("Fase: {:?}", state.phase)),
-            ]
-        } else {
-            column![text("Jogo não iniciado")]
+        // Conteúdo da tela atual
+        let content: Element<Message> = match self.screen {
+            Screen::Login => self.login.view(),
+            Screen::Lobby => self.lobby.view(),
+            Screen::Game => self.game_view(),
         };
 
         // Log de mensagens
@@ -394,12 +1121,7 @@ impl RpgAsciiClient {
             column![
                 title,
                 status,
-                controls,
-                row![
-                    board_view,
-                    container(game_info).padding(20),
-                ]
-                .spacing(20),
+                content,
                 container(log_view).padding(10),
             ]
             .spacing(20)
@@ -413,6 +1135,483 @@ impl RpgAsciiClient {
 }
 
 impl RpgAsciiClient {
+    /// Renderiza a tela da partida (controles, tabuleiro e informações)
+    fn game_view(&self) -> Element<'_, Message> {
+        // Botões de controle
+        let controls = row![
+            button("Lobby").on_press(Message::OpenLobby),
+            button("Finalizar Turno").on_press(Message::SendAction(Action::EndTurn)),
+            button("IA Jogar").on_press(Message::RequestAiMove),
+            button("Dica").on_press(Message::RequestHint),
+            button(if self.auto_play { "⏸ Auto IA" } else { "▶ Auto IA" })
+                .on_press(Message::ToggleAutoPlay),
+            button(text(format!("IA: {}", ai_source_label(self.ai_source))))
+                .on_press_maybe(self.is_server_match().then_some(Message::ToggleAiSource)),
+            button("Limpar Seleção").on_press(Message::ClearSelection),
+            button("Atalhos").on_press(Message::ToggleHelp),
+            button("Cancelar").on_press_maybe(
+                matches!(self.input_mode, InputMode::WaitingResponse).then_some(Message::CancelAction)
+            ),
+        ]
+        .push_maybe(
+            self.blocked_action
+                .as_ref()
+                .map(|_| button("🐞 Enviar mesmo assim").on_press(Message::ForceSendAction)),
+        )
+        .spacing(10);
+        
+        // IA vs IA: só os controles de reprodução
+        let controls: Element<Message> = match &self.spectator {
+            Some(spectator) => row![
+                button("Lobby").on_press(Message::OpenLobby),
+                spectator.controls_view(self.is_game_over()),
+            ]
+            .spacing(10)
+            .into(),
+            None => controls.into(),
+        };
+        
+        // Troca de turno no hot-seat: esconde o tabuleiro
+        if let Some(player) = self.hot_seat.as_ref().and_then(|hot_seat| hot_seat.handoff_to.as_deref()) {
+            return HotSeat::handoff_view(player);
+        }
+        
+        // Renderiza tabuleiro
+        let board_view = if let Some(state) = &self.game_state {
+            let targets = self
+                .targets
+                .as_ref()
+                .map(|(_, targets)| targets.clone())
+                .unwrap_or_default();
+            
+            BoardRenderer::render(
+                &state.board,
+                Highlights {
+                    selected: self.selected_coord,
+                    hint: self.hint,
+                    last_move: self.last_move,
+                    cursor: Some(self.input.cursor),
+                    moves: targets.moves,
+                    attacks: targets.attacks,
+                },
+                &self.palette,
+                &self.owner_colors,
+                self.animation.as_ref(),
+                &self.board_cache,
+            )
+        } else {
+            container(text("Aguardando conexão..."))
+                .width(Length::Fill)
+                .height(400)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into()
+        };
+        
+        // Informações do jogo
+        let game_info = if let Some(state) = &self.game_state {
+            column![
+                text(format!("Turno: {}", state.turn)),
+                text(format!("Contador: {}", state.turn_count)),
+                text(format!("Fase: {:?}", state.phase)),
+            ]
+        } else {
+            column![text("Jogo não iniciado")]
+        };
+        
+        let board_row = row![
+            board_view,
+            container(game_info).padding(20),
+        ]
+        .spacing(20);
+        
+        // Ajuda por cima do tabuleiro, que continua visível ao fundo
+        let board_row: Element<Message> = if self.show_help {
+            stack![board_row, opaque(ui::help_overlay(&self.keymap))].into()
+        } else {
+            board_row.into()
+        };
+        
+        column![
+            controls,
+            board_row,
+        ]
+        .spacing(20)
+        .into()
+    }
+
+    /// Reage a cada tipo de erro de rede de forma diferente
+    fn handle_net_error(&mut self, e: NetError) {
+        match e {
+            NetError::Rejected(reason) => {
+                // Regra violada: o estado local continua válido
+                warn!("⚠️ Ação rejeitada: {}", reason);
+                self.add_log(format!("Ação inválida: {}", reason));
+            }
+            NetError::Unauthorized => {
+                self.expire_session();
+            }
+            NetError::Transport(_) | NetError::Timeout(_) => {
+                error!("❌ Servidor inacessível: {}", e);
+                self.connection_status = ConnectionStatus::Error(e.to_string());
+                self.add_log(format!("Servidor inacessível: {}", e));
+            }
+            NetError::Http { status, ref body } => {
+                error!("❌ Erro HTTP {}: {}", status, body);
+                self.add_log(format!("Erro do servidor ({}): {}", status, body));
+            }
+            NetError::Decode(ref detail) => {
+                error!("❌ Resposta inválida: {}", detail);
+                self.add_log("Resposta inválida do servidor (versões incompatíveis?)".to_string());
+            }
+            NetError::Local(ref detail) => {
+                error!("❌ {}", detail);
+                self.add_log(detail.clone());
+            }
+        }
+    }
+
+    /// Backend padrão: engine local no modo offline, senão o servidor
+    fn default_backend(&self) -> Arc<dyn GameBackend> {
+        if self.config.offline {
+            Arc::new(self.local.clone())
+        } else {
+            Arc::new(self.network.clone())
+        }
+    }
+
+    /// Envia a ação em nome de `player_id` (o jogador local ou a IA); a
+    /// resposta chega em `on_result`
+    fn send_action_as(
+        &mut self,
+        player_id: String,
+        action: Action,
+        on_result: fn(Result<GameState, NetError>) -> Message,
+    ) -> Task<Message> {
+        let Some(match_id) = self.match_id.clone() else {
+            self.add_log("Não conectado!".to_string());
+            return Task::none();
+        };
+        
+        info!("📤 Enviando ação: {:?}", action);
+        self.add_log(format!("Enviando: {:?}", action));
+        
+        // Log detalhado ANTES do request
+        info!(
+            "Client -> POST /action {{player: {}, action: {:?}}}",
+            player_id, action
+        );
+        
+        self.input_mode = InputMode::WaitingResponse;
+        let (task, handle) = Task::perform(
+            self.backend.send_action(match_id, player_id, action),
+            on_result,
+        )
+        .abortable();
+        self.pending_action = Some(handle);
+        
+        task
+    }
+
+    /// Resposta de uma ação enviada, do jogador ou da IA
+    fn apply_action_result(&mut self, result: Result<GameState, NetError>) {
+        self.pending_action = None;
+        match result {
+            Ok(new_state) => {
+                info!("✅ Ação aceita pelo servidor");
+                if let ConnectionStatus::Connecting | ConnectionStatus::Error(_) = self.connection_status {
+                    self.connection_status = ConnectionStatus::Connected;
+                }
+                self.set_game_state(new_state);
+                self.add_log("Ação executada com sucesso!".to_string());
+                self.sync_hot_seat_turn();
+            }
+            Err(e) => self.handle_net_error(e),
+        }
+        self.selected_coord = None;
+        self.input_mode = InputMode::SelectUnit;
+    }
+
+    /// Substitui o estado e registra o resultado quando a partida acaba
+    fn set_game_state(&mut self, state: GameState) {
+        let finished_now = rules::is_game_over(&state) && !self.is_game_over();
+        self.input.set_board_size(rules::board_size(&state.board));
+        self.board_cache.invalidate();
+        
+        // Estado repetido (ex.: WebSocket e resposta da ação) não gera mudanças
+        let mut changes = Vec::new();
+        let mut actor = None;
+        if let Some(previous) = &self.game_state {
+            changes = diff::diff_boards(&previous.board, &state.board);
+            // Uma ressincronização (reconexão, frame perdido) pode cobrir ações
+            // de mais de um jogador: aí as mudanças não têm autor
+            if previous.turn == state.turn || changes.len() == 1 {
+                let last_move = diff::last_move(&changes, &previous.turn, previous);
+                actor = Some((previous.turn.clone(), last_move));
+            }
+        }
+        
+        if !changes.is_empty() {
+            self.last_move = actor.as_ref().and_then(|(_, last_move)| *last_move);
+            if let Some(summary) = diff::summarize(&changes) {
+                let line = match &actor {
+                    Some((player, _)) => format!("{}: {}", player, summary),
+                    None => summary,
+                };
+                info!("📋 {}", line);
+                self.add_log(line);
+            }
+            if self.config.animations {
+                let duration = Duration::from_millis(self.config.animation_ms);
+                self.animation = Some(Animation::new(changes, duration));
+            }
+        }
+        self.game_state = Some(state);
+        self.refresh_owner_colors();
+        // A dica e os destinos valiam para o estado anterior
+        self.hint = None;
+        self.targets = None;
+        
+        if finished_now {
+            let result = match self.game_state.as_ref().and_then(rules::winner) {
+                Some(winner) => format!("Fim de jogo: {} venceu", winner),
+                None => "Fim de jogo".to_string(),
+            };
+            info!("🏁 {}", result);
+            self.add_log(result);
+        }
+    }
+
+    /// Cores dos jogadores da partida, dos donos no tabuleiro e das unidades
+    /// que ainda estão sumindo na animação
+    fn refresh_owner_colors(&mut self) {
+        let Some(state) = &self.game_state else {
+            return;
+        };
+        
+        let players = state.players.iter().map(String::as_str);
+        let on_board = rules::coords(&state.board).filter_map(|coord| rules::owner_at(state, &coord));
+        let fading = self
+            .animation
+            .iter()
+            .flat_map(|animation| &animation.changes)
+            .filter_map(|change| match change {
+                BoardChange::Destroyed { unit, .. } => Some(unit.owner.as_str()),
+                _ => None,
+            });
+        
+        self.owner_colors = OwnerColors::new(&self.palette, players.chain(on_board).chain(fading));
+    }
+
+    /// `match_id` ainda é a partida aberta: resultados que chegam depois de
+    /// trocar de partida são descartados
+    fn is_current_match(&self, match_id: &str) -> bool {
+        let current = self.match_id.as_deref() == Some(match_id);
+        if !current {
+            debug!("Resultado da partida {} descartado", match_id);
+        }
+        current
+    }
+
+    /// Esquece a partida atual, cancelando a ação que estiver em andamento
+    fn leave_match(&mut self) {
+        if let Some(match_id) = self.match_id.take() {
+            info!("🚪 Saindo da partida {}", match_id);
+        }
+        if let Some(handle) = self.pending_action.take() {
+            handle.abort();
+        }
+        self.game_state = None;
+        self.targets = None;
+        self.last_move = None;
+        self.animation = None;
+        self.hint = None;
+        self.blocked_action = None;
+        self.selected_coord = None;
+        self.input_mode = InputMode::SelectUnit;
+        self.ai_busy = false;
+    }
+
+    /// Motivo pelo qual `action` não está entre os destinos destacados de
+    /// `from`, ou `None` se estiver
+    fn explain_unreachable(&self, from: Coord, action: &Action) -> Option<String> {
+        let state = self.game_state.as_ref()?;
+        let (_, targets) = self.targets.as_ref().filter(|(selected, _)| *selected == from)?;
+        
+        let (to, highlighted, kind) = match *action {
+            Action::Move { to, .. } => (to, &targets.moves, "mover"),
+            Action::Attack { to, .. } => (to, &targets.attacks, "atacar"),
+            _ => return None,
+        };
+        if highlighted.contains(&to) {
+            return None;
+        }
+        
+        let reason = match rules::try_apply(state, &self.player_id, action) {
+            Err(e) => e,
+            Ok(_) => "destino fora das casas destacadas".to_string(),
+        };
+        Some(format!("Não dá para {} de {} para {}: {}", kind, from, to, reason))
+    }
+
+    /// Recalcula os destinos destacados se a unidade selecionada mudou ou se
+    /// o estado foi substituído
+    fn sync_targets(&mut self) {
+        let from = match self.input_mode {
+            InputMode::SelectTarget => self.selected_coord,
+            _ => None,
+        };
+        if self.targets.as_ref().map(|(selected, _)| *selected) == from {
+            return;
+        }
+        
+        self.targets = match (from, &self.game_state) {
+            (Some(from), Some(state)) => Some((from, rules::targets(state, &self.player_id, from))),
+            _ => None,
+        };
+    }
+
+    /// A partida atual terminou
+    fn is_game_over(&self) -> bool {
+        self.game_state.as_ref().is_some_and(rules::is_game_over)
+    }
+
+    /// O turno atual pertence a um participante controlado pela IA
+    fn is_ai_turn(&self) -> bool {
+        self.game_state.as_ref().is_some_and(|state| match &self.spectator {
+            Some(spectator) => spectator.players.contains(&state.turn),
+            None => state.turn != self.player_id && self.config.ai_players.contains(&state.turn),
+        })
+    }
+
+    /// Jogo automático ligado (no modo espectador, reprodução não pausada)
+    fn auto_play_enabled(&self) -> bool {
+        match &self.spectator {
+            Some(spectator) => !spectator.paused,
+            None => self.auto_play,
+        }
+    }
+
+    fn pause_auto_play(&mut self) {
+        match &mut self.spectator {
+            Some(spectator) => spectator.paused = true,
+            None => self.auto_play = false,
+        }
+    }
+
+    /// Agenda a próxima jogada da IA, com espera para ficar visível
+    fn schedule_ai_turn(&mut self) -> Task<Message> {
+        let Some(match_id) = self.match_id.clone() else {
+            return Task::none();
+        };
+        if !self.auto_play_enabled()
+            || self.ai_busy
+            || self.is_server_match()
+            || self.screen != Screen::Game
+            || self.is_game_over()
+            || !self.is_ai_turn()
+        {
+            return Task::none();
+        }
+        
+        self.ai_busy = true;
+        let base_ms = self.config.ai_move_delay_ms;
+        let delay_ms = self
+            .spectator
+            .as_ref()
+            .map_or(base_ms, |spectator| spectator.delay_ms(base_ms));
+        Task::perform(tokio::time::sleep(Duration::from_millis(delay_ms)), move |_| {
+            Message::AiTurnTick(match_id.clone())
+        })
+    }
+
+    /// Pede a próxima ação de quem tem o turno (servidor ou IA local)
+    fn request_ai_move(&mut self) -> Task<Message> {
+        let (Some(match_id), Some(state)) = (&self.match_id, &self.game_state) else {
+            self.ai_busy = false;
+            return Task::none();
+        };
+        
+        if (state.turn == self.player_id && self.spectator.is_none()) || rules::is_game_over(state) {
+            self.ai_busy = false;
+            return Task::none();
+        }
+        
+        // O servidor joga pela própria IA; só resta buscar o estado
+        if self.is_server_match() {
+            self.ai_busy = false;
+            self.add_log("A IA do servidor joga sozinha, atualizando estado...".to_string());
+            return Task::perform(async {}, |_| Message::RefreshState);
+        }
+        
+        self.ai_busy = true;
+        let ai_player = state.turn.clone();
+        let (move_for, label) = (match_id.clone(), ai_player.clone());
+        let on_result = move |result| Message::AiMoveResult(move_for.clone(), label.clone(), result);
+        
+        match self.ai_source {
+            AiSource::Server => {
+                info!("🤖 Solicitando movimento da IA...");
+                Task::perform(self.backend.get_ai_action(match_id.clone(), ai_player), on_result)
+            }
+            AiSource::Local => {
+                info!("🤖 IA local pensando...");
+                Task::perform(ai::think(state.clone(), ai_player, self.config.ai_limits()), on_result)
+            }
+        }
+    }
+
+    /// Fonte de IA ao entrar numa partida: sem servidor, só a local
+    fn default_ai_source(&self) -> AiSource {
+        if self.is_server_match() {
+            self.config.ai_source
+        } else {
+            AiSource::Local
+        }
+    }
+
+    /// A partida atual está no servidor (não é offline, hot-seat nem espectador)
+    fn is_server_match(&self) -> bool {
+        !self.config.offline && self.hot_seat.is_none() && self.spectator.is_none()
+    }
+
+    /// Há uma troca de jogador esperando confirmação
+    fn handoff_pending(&self) -> bool {
+        self.hot_seat
+            .as_ref()
+            .is_some_and(|hot_seat| hot_seat.handoff_to.is_some())
+    }
+
+    /// No hot-seat, passa o controle para quem tem o turno agora
+    fn sync_hot_seat_turn(&mut self) {
+        let Some(turn) = self.game_state.as_ref().map(|state| state.turn.clone()) else {
+            return;
+        };
+        let Some(hot_seat) = &mut self.hot_seat else {
+            return;
+        };
+        
+        if turn != self.player_id && hot_seat.has_player(&turn) {
+            info!("🪑 Turno passa para {}", turn);
+            self.player_id = turn.clone();
+            self.selected_coord = None;
+            self.input_mode = InputMode::SelectUnit;
+            hot_seat.handoff_to = Some(turn);
+        }
+    }
+
+    /// Token recusado: apaga a sessão e volta para o login
+    fn expire_session(&mut self) {
+        warn!("🔑 Sessão expirada para {}", self.player_id);
+        self.token_store.clear();
+        self.network.set_token(None);
+        self.backend = self.default_backend();
+        self.login.player_id = self.player_id.clone();
+        self.screen = Screen::Login;
+        self.input_mode = InputMode::SelectUnit;
+        self.add_log("Sessão expirada, faça login novamente".to_string());
+    }
+
     fn add_log(&mut self, message: String) {
         let timestamp = chrono::Local::now().format("%H:%M:%S");
         self.message_log.push(format!("[{}] {}", timestamp, message));
@@ -424,21 +1623,166 @@ impl RpgAsciiClient {
     }
 }
 
-fn init_logging() {
+/// Teclas não consumidas por outro widget (campos de texto, por exemplo)
+fn keyboard_events() -> Subscription<Message> {
+    event::listen_with(|event, status, _window| match (&event, status) {
+        (Event::Keyboard(_), event::Status::Ignored) => Some(Message::KeyboardEvent(event)),
+        _ => None,
+    })
+}
+
+fn ai_source_label(source: AiSource) -> &'static str {
+    match source {
+        AiSource::Server => "servidor",
+        AiSource::Local => "local",
+    }
+}
+
+fn init_logging(filter: &str) {
     use tracing_subscriber::fmt;
     use tracing_subscriber::EnvFilter;
     
-    fmt()
-        .with_env_filter(EnvFilter::new("info,client=debug"))
-        .init();
+    // Só o primeiro cliente do processo instala o subscriber (testes criam vários)
+    let _ = fmt()
+        .with_env_filter(EnvFilter::new(filter))
+        .try_init();
 }
 
 fn main() -> iced::Result {
+    let config = match Config::load(Cli::parse()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Erro na configuração: {:#}", e);
+            std::process::exit(2);
+        }
+    };
+    
     iced::application(
         "RPG ASCII Tático - Cliente",
         RpgAsciiClient::update,
         RpgAsciiClient::view,
     )
-    .theme(|_| Theme::Dark)
-    .run_with(RpgAsciiClient::new)
+    .subscription(RpgAsciiClient::subscription)
+    .theme(RpgAsciiClient::theme)
+    .run_with(move || RpgAsciiClient::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cliente offline com uma partida local, sem ler nem gravar em disco
+    fn offline_client() -> RpgAsciiClient {
+        let config = Config {
+            offline: true,
+            player_id: "player1".to_string(),
+            ..Default::default()
+        };
+        let local = LocalBackend::new(config.ai_limits());
+        let match_id = local.create("player1", "player2");
+        let state = local.state(&match_id).unwrap();
+
+        let (mut app, _) = RpgAsciiClient::from_parts(config, TokenStore::disabled(), local);
+        app.match_id = Some(match_id);
+        app.screen = Screen::Game;
+        app.set_game_state(state);
+        app
+    }
+
+    #[test]
+    fn test_target_selection_transitions() {
+        let mut app = offline_client();
+        let state = app.game_state.clone().unwrap();
+        let owned_by = |player: &str| -> Vec<Coord> {
+            rules::coords(&state.board)
+                .filter(|coord| rules::owner_at(&state, coord) == Some(player))
+                .collect()
+        };
+        let (own, enemy) = (owned_by("player1"), owned_by("player2"));
+
+        let _ = app.update(Message::CellClicked(own[0]));
+        assert_eq!(app.selected_coord, Some(own[0]));
+        assert!(matches!(app.input_mode, InputMode::SelectTarget));
+
+        // Outra unidade própria troca a seleção em vez de atacá-la
+        let _ = app.update(Message::CellClicked(own[1]));
+        assert_eq!(app.selected_coord, Some(own[1]));
+        assert!(matches!(app.input_mode, InputMode::SelectTarget));
+
+        // A unidade já selecionada desfaz a seleção
+        let _ = app.update(Message::CellClicked(own[1]));
+        assert_eq!(app.selected_coord, None);
+        assert!(matches!(app.input_mode, InputMode::SelectUnit));
+
+        // Inimigo fora de alcance: explica e mantém a seleção
+        let _ = app.update(Message::CellClicked(own[0]));
+        let far = enemy
+            .iter()
+            .copied()
+            .find(|coord| !rules::targets(&state, "player1", own[0]).attacks.contains(coord))
+            .unwrap();
+        let _ = app.update(Message::CellClicked(far));
+        assert_eq!(app.selected_coord, Some(own[0]));
+        assert!(matches!(app.input_mode, InputMode::SelectTarget));
+
+        // Inimigo ao alcance: o ataque é enviado
+        let near = approach_enemy(state);
+        let (from, target) = rules::coords(&near.board)
+            .filter(|coord| rules::owner_at(&near, coord) == Some("player1"))
+            .find_map(|from| {
+                let attacks = rules::targets(&near, "player1", from).attacks;
+                attacks.first().map(|target| (from, *target))
+            })
+            .unwrap();
+        let _ = app.update(Message::ClearSelection);
+        app.set_game_state(near);
+        let _ = app.update(Message::CellClicked(from));
+        let _ = app.update(Message::CellClicked(target));
+        assert!(matches!(app.input_mode, InputMode::WaitingResponse));
+    }
+
+    /// Aproxima as unidades dos dois lados até player1 ter um ataque no seu turno
+    fn approach_enemy(mut state: GameState) -> GameState {
+        let distance = |state: &GameState| {
+            let units = |player: &str| -> Vec<Coord> {
+                rules::coords(&state.board)
+                    .filter(|coord| rules::owner_at(state, coord) == Some(player))
+                    .collect()
+            };
+            let (own, enemy) = (units("player1"), units("player2"));
+            own.iter()
+                .flat_map(|a| enemy.iter().map(move |b| a.x.abs_diff(b.x) + a.y.abs_diff(b.y)))
+                .min()
+                .unwrap_or(usize::MAX)
+        };
+
+        for _ in 0..200 {
+            let player = state.turn.clone();
+            let actions = rules::legal_actions(&state, &player);
+            let can_attack = actions
+                .iter()
+                .any(|(action, _)| matches!(action, Action::Attack { .. }));
+            if player == "player1" && can_attack {
+                return state;
+            }
+
+            // Um passo que aproxima os lados; sem nenhum, passa a vez
+            let current = distance(&state);
+            let (_, next) = actions
+                .into_iter()
+                .filter(|(action, next)| {
+                    matches!(action, Action::Move { .. }) && distance(next) < current
+                })
+                .min_by_key(|(_, next)| distance(next))
+                .or_else(|| {
+                    rules::try_apply(&state, &player, &Action::EndTurn)
+                        .ok()
+                        .map(|next| (Action::EndTurn, next))
+                })
+                .unwrap();
+            state = next;
+        }
+
+        panic!("as unidades não chegaram ao alcance de ataque");
+    }
 }
@@ -1,103 +1,223 @@
-use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
 use tatic_lib::{Action, GameState};
-use reqwest::Client;
-use serde_json::json;
-use tracing::{debug, info};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::api::{
+    ActionRequest, AiActionRequest, ApiResponse, CreateMatchRequest, CreateMatchResponse,
+    LoginRequest, LoginResponse, MatchSummary, StateQuery,
+};
+
+/// Erros de comunicação com o servidor
+#[derive(Debug, Clone, Error)]
+pub enum NetError {
+    /// Falha de transporte (conexão recusada, DNS, conexão caiu)
+    #[error("Erro de conexão: {0}")]
+    Transport(String),
+    /// O servidor não respondeu a tempo
+    #[error("Tempo esgotado: {0}")]
+    Timeout(String),
+    /// Resposta HTTP com status de erro
+    #[error("Erro HTTP {status}: {body}")]
+    Http { status: u16, body: String },
+    /// Token ausente, inválido ou expirado (HTTP 401)
+    #[error("Sessão expirada, faça login novamente")]
+    Unauthorized,
+    /// O servidor recusou a requisição (campo `error` da resposta)
+    #[error("Rejeitado pelo servidor: {0}")]
+    Rejected(String),
+    /// Resposta em formato inesperado
+    #[error("Erro ao decodificar: {0}")]
+    Decode(String),
+    /// Falha no próprio cliente (ex.: a tarefa da IA local caiu)
+    #[error("Erro local: {0}")]
+    Local(String),
+}
+
+impl From<reqwest::Error> for NetError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            NetError::Timeout(e.to_string())
+        } else if e.is_decode() {
+            NetError::Decode(e.to_string())
+        } else {
+            NetError::Transport(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for NetError {
+    fn from(e: serde_json::Error) -> Self {
+        NetError::Decode(e.to_string())
+    }
+}
+
+impl NetError {
+    /// Erros transitórios, que podem sumir ao repetir a requisição
+    pub fn is_transient(&self) -> bool {
+        match self {
+            NetError::Transport(_) | NetError::Timeout(_) => true,
+            NetError::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// Timeouts e política de retry das requisições
+#[derive(Clone, Copy, Debug)]
+pub struct RequestPolicy {
+    /// Tempo máximo para estabelecer a conexão TCP/TLS
+    pub connect_timeout: Duration,
+    /// Tempo máximo sem receber dados da resposta
+    pub read_timeout: Duration,
+    /// Repetições extras para chamadas idempotentes
+    pub max_retries: u32,
+}
+
+/// Espera base antes da primeira repetição
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
 
 #[derive(Clone, Debug)]
 pub struct NetworkClient {
     base_url: String,
-    #[allow(dead_code)]
     client: Client,
+    /// Bearer token enviado em todas as requisições
+    token: Option<String>,
+    policy: RequestPolicy,
 }
 
 impl NetworkClient {
-    pub fn new(base_url: String) -> Self {
+    /// Cria o cliente com timeouts e retries customizados
+    pub fn with_policy(base_url: String, policy: RequestPolicy) -> Self {
+        let client = Client::builder()
+            .connect_timeout(policy.connect_timeout)
+            .read_timeout(policy.read_timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                warn!("⚠️ Erro ao configurar cliente HTTP, usando padrão: {}", e);
+                Client::new()
+            });
+        
         Self {
             base_url,
-            client: Client::new(),
+            client,
+            token: None,
+            policy,
         }
     }
 
-    /// Conecta ao servidor e obtém/cria uma partida
-    pub async fn connect(&self) -> Result<String, String> {
-        info!("Conectando ao servidor: {}", self.base_url);
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Define (ou remove) o token usado nas próximas requisições
+    pub fn set_token(&mut self, token: Option<String>) {
+        self.token = token;
+    }
+
+    /// Troca credenciais por um token em `POST /auth/login`
+    pub async fn login(&self, player_id: &str, password: &str) -> Result<String, NetError> {
+        let url = format!("{}/auth/login", self.base_url);
+        
+        info!("Autenticando jogador: {}", player_id);
         
-        // Lista partidas existentes
-        let url = format!("{}/matches", self.base_url);
         let response = self.client
-            .get(&url)
+            .post(&url)
+            .json(&LoginRequest { player_id, password })
             .send()
-            .await
-            .map_err(|e| format!("Erro de conexão: {}", e))?;
-        
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Erro ao decodificar: {}", e))?;
-        
-        // Pega primeira partida ou cria nova
-        if let Some(matches) = data["data"].as_array() {
-            if !matches.is_empty() {
-                if let Some(match_id) = matches[0]["id"].as_str() {
-                    info!("Usando partida existente: {}", match_id);
-                    return Ok(match_id.to_string());
-                }
-            }
+            .await?;
+        
+        let login: LoginResponse = read_envelope(response).await?;
+        Ok(login.token)
+    }
+
+    /// Requisição GET com o header `Authorization`
+    fn get(&self, url: &str) -> RequestBuilder {
+        self.authorize(self.client.get(url))
+    }
+
+    /// Requisição POST com o header `Authorization`
+    fn post(&self, url: &str) -> RequestBuilder {
+        self.authorize(self.client.post(url))
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
         }
+    }
+
+    /// URL do WebSocket de atualizações ao vivo da partida
+    pub fn ws_url(&self, match_id: &str) -> String {
+        let base = if let Some(rest) = self.base_url.strip_prefix("https://") {
+            format!("wss://{}", rest)
+        } else if let Some(rest) = self.base_url.strip_prefix("http://") {
+            format!("ws://{}", rest)
+        } else {
+            self.base_url.clone()
+        };
+
+        format!("{}/ws?match_id={}", base, match_id)
+    }
+
+    /// Lista as partidas existentes no servidor
+    pub async fn list_matches(&self) -> Result<Vec<MatchSummary>, NetError> {
+        let url = &format!("{}/matches", self.base_url);
         
-        // Cria nova partida
-        self.create_match("player1", "ai").await
+        debug!("GET {}", url);
+        
+        self.with_retry("GET /matches", || async move {
+            let response = self
+                .get(url)
+                .send()
+                .await?;
+            
+            read_envelope(response).await
+        })
+        .await
     }
 
     /// Cria nova partida
-    pub async fn create_match(&self, player1: &str, player2: &str) -> Result<String, String> {
+    pub async fn create_match(&self, player1: &str, player2: &str) -> Result<String, NetError> {
         let url = format!("{}/match/create", self.base_url);
         
-        let response = self.client
+        info!("Criando partida: {} vs {}", player1, player2);
+        
+        let response = self
             .post(&url)
-            .json(&json!({
-                "player1": player1,
-                "player2": player2
-            }))
+            .json(&CreateMatchRequest { player1, player2 })
             .send()
-            .await
-            .map_err(|e| format!("Erro ao criar partida: {}", e))?;
+            .await?;
         
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Erro ao decodificar: {}", e))?;
-        
-        data["data"]
-            .as_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| "ID da partida não retornado".to_string())
+        let created: CreateMatchResponse = read_envelope(response).await?;
+        Ok(created.match_id)
     }
 
     /// Obtém estado atual da partida
-    pub async fn get_state(&self, match_id: &str) -> Result<GameState, String> {
-        let url = format!("{}/state?match_id={}", self.base_url, match_id);
-        
-        debug!("GET {}", url);
+    pub async fn get_state(&self, match_id: &str) -> Result<GameState, NetError> {
+        let url = &format!("{}/state", self.base_url);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Erro ao obter estado: {}", e))?;
+        debug!("GET {}?match_id={}", url, match_id);
         
-        if !response.status().is_success() {
-            return Err(format!("Erro HTTP: {}", response.status()));
-        }
-        
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Erro ao decodificar: {}", e))?;
-        
-        serde_json::from_value(data["data"].clone())
-            .map_err(|e| format!("Erro ao parsear estado: {}", e))
+        self.with_retry("GET /state", || async move {
+            let response = self
+                .get(url)
+                .query(&StateQuery { match_id })
+                .send()
+                .await?;
+            
+            read_envelope(response).await
+        })
+        .await
     }
 
     /// Envia ação para o servidor
@@ -106,62 +226,101 @@ impl NetworkClient {
         match_id: &str,
         player_id: &str,
         action: Action,
-    ) -> Result<GameState, String> {
+    ) -> Result<GameState, NetError> {
         let url = format!("{}/action", self.base_url);
         
-        let body = json!({
-            "match_id": match_id,
-            "player_id": player_id,
-            "action": action
-        });
+        let body = ActionRequest {
+            match_id,
+            player_id,
+            action: &action,
+        };
         
-        debug!("POST {} - Body: {}", url, body);
+        debug!("POST {} - Body: {:?}", url, body);
         
-        let response = self.client
+        let response = self
             .post(&url)
             .json(&body)
             .send()
-            .await
-            .map_err(|e| format!("Erro ao enviar ação: {}", e))?;
+            .await?;
         
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(format!("Erro HTTP: {}", error_text));
-        }
-        
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Erro ao decodificar: {}", e))?;
-        
-        if !data["success"].as_bool().unwrap_or(false) {
-            return Err(data["error"].as_str().unwrap_or("Erro desconhecido").to_string());
-        }
-        
-        serde_json::from_value(data["data"].clone())
-            .map_err(|e| format!("Erro ao parsear estado: {}", e))
+        read_envelope(response).await
     }
 
-    /// Solicita ação da IA
-    pub async fn get_ai_action(&self, match_id: &str, ai_player: &str) -> Result<Action, String> {
+    /// Solicita ação da IA (só sugestão: o servidor aplica as jogadas da
+    /// própria IA)
+    pub async fn get_ai_action(&self, match_id: &str, ai_player: &str) -> Result<Action, NetError> {
         let url = format!("{}/ai/action", self.base_url);
         
-        let response = self.client
+        let response = self
             .post(&url)
-            .json(&json!({
-                "match_id": match_id,
-                "ai_player": ai_player
-            }))
+            .json(&AiActionRequest { match_id, ai_player })
             .send()
-            .await
-            .map_err(|e| format!("Erro ao solicitar IA: {}", e))?;
+            .await?;
         
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Erro ao decodificar: {}", e))?;
+        read_envelope(response).await
+    }
+}
+
+impl NetworkClient {
+    /// Repete uma chamada idempotente enquanto o erro for transitório
+    ///
+    /// A espera dobra a cada tentativa e recebe um jitter aleatório de até
+    /// 100%, para que vários clientes não martelem o servidor ao mesmo tempo.
+    async fn with_retry<T, F, Fut>(&self, label: &str, mut request: F) -> Result<T, NetError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, NetError>>,
+    {
+        let mut attempt = 0;
         
-        serde_json::from_value(data["data"].clone())
-            .map_err(|e| format!("Erro ao parsear ação: {}", e))
+        loop {
+            match request().await {
+                Err(e) if e.is_transient() && attempt < self.policy.max_retries => {
+                    attempt += 1;
+                    let base = RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt - 1));
+                    let jitter = base.mul_f64(rand::random::<f64>());
+                    warn!(
+                        "⚠️ {} falhou ({}), tentativa {}/{} em {:?}",
+                        label, e, attempt, self.policy.max_retries, base + jitter
+                    );
+                    tokio::time::sleep(base + jitter).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Lê o envelope `{success, data, error}` e decodifica o campo `data`
+///
+/// Status HTTP de erro viram `NetError::Http`, exceto quando o corpo traz o
+/// envelope com `error` preenchido, que vira `NetError::Rejected`. Campos
+/// renomeados ou ausentes no servidor aparecem como `NetError::Decode`.
+async fn read_envelope<T: DeserializeOwned>(response: Response) -> Result<T, NetError> {
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(NetError::Unauthorized);
+    }
+    
+    let body = response.text().await?;
+    
+    let envelope: ApiResponse<T> = match serde_json::from_str(&body) {
+        Ok(envelope) => envelope,
+        Err(_) if !status.is_success() => {
+            return Err(NetError::Http { status: status.as_u16(), body });
+        }
+        Err(e) => return Err(e.into()),
+    };
+    
+    if !envelope.success {
+        return match envelope.error {
+            Some(error) => Err(NetError::Rejected(error)),
+            None if !status.is_success() => Err(NetError::Http { status: status.as_u16(), body }),
+            None => Err(NetError::Decode("Resposta sem sucesso e sem erro".to_string())),
+        };
     }
+    
+    envelope
+        .data
+        .ok_or_else(|| NetError::Decode("Campo data ausente".to_string()))
 }
@@ -1,85 +1,408 @@
+use std::cell::RefCell;
+use std::fmt;
+
 use tatic_lib::{Board, Cell, Coord};
 use iced::{
-    widget::{button, container, text},
-    Color, Length,
+    alignment, mouse,
+    widget::canvas::{self, Cache, Frame, Path, Stroke},
+    Color, Element, Length, Point, Rectangle, Renderer, Size, Theme, Vector,
 };
 
+use crate::animation::Animation;
+use crate::diff::BoardChange;
+use crate::rules;
+use crate::theme::{OwnerColors, Palette};
 use crate::Message;
 
+/// Lado de cada casa, em pixels
+const CELL_SIZE: f32 = 44.0;
+/// Espaço das coordenadas à esquerda e no topo
+const LABEL_SIZE: f32 = 28.0;
+
 pub struct BoardRenderer;
 
-impl BoardRenderer {
-    /// Renderiza o tabuleiro como grid de botões
-    pub fn render(board: &Board, selected: Option<Coord>) -> iced::widget::Container<'static, Message> {
-        use iced::widget::{column, row};
-        
-        let mut grid = column![];
-        
-        // Header com coordenadas
-        let mut header = row![];
-        header = header.push(text("  ").size(20));
-        for x in 0..8 {
-            header = header.push(
-                container(text(format!("{}", x)).size(16))
-                    .width(40)
-                    .center_x(Length::Fill)
-            );
-        }
-        grid = grid.push(header);
-        
-        // Células do tabuleiro
-        for y in 0..8 {
-            let mut row_widgets = row![];
-            
-            // Número da linha
-            row_widgets = row_widgets.push(
-                container(text(format!("{} ", y)).size(16))
-                    .width(20)
-            );
-            
-            // Células
-            for x in 0..8 {
-                let coord = Coord::new(x, y);
-                let cell = board.get(&coord).unwrap();
-                
-                let symbol = match cell {
-                    Cell::Unit(unit) => unit.symbol.to_string(),
-                    Cell::Empty => ".".to_string(),
-                };
-                
-                let mut cell_button = button(text(symbol).size(20))
-                    .on_press(Message::CellClicked(coord))
-                    .width(40)
-                    .height(40);
-                
-                // Destaca célula selecionada com estilo customizado
-                if Some(coord) == selected {
-                    cell_button = cell_button.style(move |_theme, _status| {
-                        iced::widget::button::Style {
-                            background: Some(iced::Background::Color(Color::from_rgb(0.2, 0.6, 1.0))),
-                            text_color: Color::WHITE,
-                            border: iced::Border {
-                                color: Color::from_rgb(0.0, 0.4, 0.8),
-                                width: 2.0,
-                                radius: 4.0.into(),
-                            },
-                            ..Default::default()
-                        }
-                    });
-                }
-                
-                row_widgets = row_widgets.push(cell_button);
-            }
-            
-            grid = grid.push(row_widgets);
+/// Células destacadas sobre o tabuleiro
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Highlights {
+    /// Unidade selecionada
+    pub selected: Option<Coord>,
+    /// Origem e destino de uma jogada sugerida
+    pub hint: Option<(Coord, Coord)>,
+    /// Origem e destino da última ação, de qualquer jogador
+    pub last_move: Option<(Coord, Coord)>,
+    /// Cursor do teclado, com borda visível sobre qualquer outro destaque
+    pub cursor: Option<Coord>,
+    /// Casas para onde a unidade selecionada pode se mover
+    pub moves: Vec<Coord>,
+    /// Inimigos que a unidade selecionada pode atacar
+    pub attacks: Vec<Coord>,
+}
+
+/// Geometria do tabuleiro guardada entre chamadas de `view()`
+///
+/// A camada do tabuleiro (casas, unidades e barras de vida) só é redesenhada
+/// após [`BoardCache::invalidate`]; a de destaques, quando eles mudam.
+pub struct BoardCache {
+    board: Cache,
+    overlays: Cache,
+    drawn_highlights: RefCell<Option<Highlights>>,
+}
+
+impl BoardCache {
+    pub fn new() -> Self {
+        Self {
+            board: Cache::new(),
+            overlays: Cache::new(),
+            drawn_highlights: RefCell::new(None),
         }
-        
-        container(grid).padding(10)
     }
-    
+
+    /// Descarta o desenho (novo `GameState` ou nova paleta)
+    pub fn invalidate(&self) {
+        self.board.clear();
+        self.overlays.clear();
+        self.drawn_highlights.replace(None);
+    }
+}
+
+impl Default for BoardCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BoardCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoardCache").finish_non_exhaustive()
+    }
+}
+
+impl BoardRenderer {
+    /// Renderiza o tabuleiro num canvas, com clique convertido em `CellClicked`
+    ///
+    /// `animation`, se houver, é desenhada por cima do estado novo a cada
+    /// quadro, sem invalidar o cache.
+    pub fn render<'a>(
+        board: &'a Board,
+        highlights: Highlights,
+        palette: &'a Palette,
+        owner_colors: &'a OwnerColors,
+        animation: Option<&'a Animation>,
+        cache: &'a BoardCache,
+    ) -> Element<'a, Message> {
+        let (width, height) = rules::board_size(board);
+        let program = BoardCanvas {
+            board,
+            size: (width, height),
+            highlights,
+            palette,
+            owner_colors,
+            animation,
+            cache,
+        };
+
+        canvas::Canvas::new(program)
+            .width(Length::Fixed(LABEL_SIZE + width as f32 * CELL_SIZE))
+            .height(Length::Fixed(LABEL_SIZE + height as f32 * CELL_SIZE))
+            .into()
+    }
+
     /// Renderiza o tabuleiro em ASCII puro (para terminal)
     pub fn render_ascii(board: &Board) -> String {
         let lines = board.to_ascii();
         lines.join("\n")
     }
 }
+
+/// Programa do canvas: desenha as camadas e faz o hit-testing dos cliques
+struct BoardCanvas<'a> {
+    board: &'a Board,
+    size: (usize, usize),
+    highlights: Highlights,
+    palette: &'a Palette,
+    owner_colors: &'a OwnerColors,
+    animation: Option<&'a Animation>,
+    cache: &'a BoardCache,
+}
+
+impl BoardCanvas<'_> {
+    /// Casa sob o ponto (relativo ao canvas), se houver
+    fn cell_at(&self, point: Point) -> Option<Coord> {
+        if point.x < LABEL_SIZE || point.y < LABEL_SIZE {
+            return None;
+        }
+
+        let x = ((point.x - LABEL_SIZE) / CELL_SIZE) as usize;
+        let y = ((point.y - LABEL_SIZE) / CELL_SIZE) as usize;
+        let coord = Coord::new(x, y);
+
+        (x < self.size.0 && y < self.size.1 && self.board.get(&coord).is_some()).then_some(coord)
+    }
+
+    fn cell_origin(coord: Coord) -> Point {
+        Point::new(
+            LABEL_SIZE + coord.x as f32 * CELL_SIZE,
+            LABEL_SIZE + coord.y as f32 * CELL_SIZE,
+        )
+    }
+
+    fn cell_center(coord: Coord) -> Point {
+        Self::cell_origin(coord) + Vector::new(CELL_SIZE / 2.0, CELL_SIZE / 2.0)
+    }
+
+    fn cell_shade(&self, coord: Coord) -> Color {
+        if (coord.x + coord.y) % 2 == 0 {
+            self.palette.cell_even
+        } else {
+            self.palette.cell_odd
+        }
+    }
+
+    /// Pinta a casa com o fundo, escondendo o que foi desenhado nela
+    fn fill_cell(&self, frame: &mut Frame, coord: Coord, color: Color) {
+        frame.fill_rectangle(
+            Self::cell_origin(coord) + Vector::new(1.0, 1.0),
+            Size::new(CELL_SIZE - 2.0, CELL_SIZE - 2.0),
+            color,
+        );
+    }
+
+    /// Casas, coordenadas, unidades e barras de vida
+    fn draw_board(&self, frame: &mut Frame) {
+        let palette = self.palette;
+        let (width, height) = self.size;
+
+        for x in 0..width {
+            frame.fill_text(label(
+                x.to_string(),
+                Point::new(LABEL_SIZE + (x as f32 + 0.5) * CELL_SIZE, LABEL_SIZE / 2.0),
+                palette.label,
+            ));
+        }
+        for y in 0..height {
+            frame.fill_text(label(
+                y.to_string(),
+                Point::new(LABEL_SIZE / 2.0, LABEL_SIZE + (y as f32 + 0.5) * CELL_SIZE),
+                palette.label,
+            ));
+        }
+
+        // Linhas da grade aparecem entre as casas
+        frame.fill_rectangle(
+            Point::new(LABEL_SIZE, LABEL_SIZE),
+            Size::new(width as f32 * CELL_SIZE, height as f32 * CELL_SIZE),
+            palette.grid,
+        );
+
+        for coord in rules::coords(self.board) {
+            // Mapas irregulares podem ter buracos: a casa fica em branco
+            let Some(cell) = self.board.get(&coord) else {
+                continue;
+            };
+
+            let origin = Self::cell_origin(coord);
+            self.fill_cell(frame, coord, self.cell_shade(coord));
+
+            let (symbol, color, unit) = match cell {
+                Cell::Unit(unit) => (unit.symbol, self.owner_colors.get(&unit.owner), Some(unit)),
+                Cell::Empty => ('.', palette.empty, None),
+            };
+            frame.fill_text(glyph(symbol, Self::cell_center(coord), color));
+
+            if let Some(unit) = unit {
+                let ratio = (unit.hp as f32 / unit.max_hp.max(1) as f32).clamp(0.0, 1.0);
+                let bar = origin + Vector::new(4.0, CELL_SIZE - 7.0);
+                let bar_width = CELL_SIZE - 8.0;
+
+                frame.fill_rectangle(bar, Size::new(bar_width, 3.0), palette.hp_empty);
+                frame.fill_rectangle(bar, Size::new(bar_width * ratio, 3.0), palette.hp_color(ratio));
+            }
+        }
+    }
+
+    /// Quadro da animação: peças deslizando, clarão no dano e peças sumindo
+    fn draw_animation(&self, frame: &mut Frame, animation: &Animation) {
+        let t = animation.progress;
+        // Desacelera no fim do trajeto
+        let eased = 1.0 - (1.0 - t) * (1.0 - t);
+
+        for change in &animation.changes {
+            match change {
+                BoardChange::Moved { unit, from, to } => {
+                    self.fill_cell(frame, *to, self.cell_shade(*to));
+                    let start = Self::cell_center(*from);
+                    let position = start + (Self::cell_center(*to) - start) * eased;
+                    frame.fill_text(glyph(unit.symbol, position, self.owner_colors.get(&unit.owner)));
+                }
+                BoardChange::Damaged { at, .. } => {
+                    let flash = Color { a: 0.8 * (1.0 - t), ..self.palette.attacks };
+                    self.fill_cell(frame, *at, flash);
+                }
+                BoardChange::Destroyed { unit, at } => {
+                    self.fill_cell(frame, *at, self.cell_shade(*at));
+                    let color = Color { a: 1.0 - t, ..self.owner_colors.get(&unit.owner) };
+                    frame.fill_text(glyph(unit.symbol, Self::cell_center(*at), color));
+                }
+            }
+        }
+    }
+
+    /// Seleção, destinos legais, dica e cursor
+    fn draw_overlays(&self, frame: &mut Frame) {
+        let highlights = &self.highlights;
+        let palette = self.palette;
+
+        for &coord in &highlights.moves {
+            self.fill_cell(frame, coord, palette.moves);
+        }
+        for &coord in &highlights.attacks {
+            self.fill_cell(frame, coord, palette.attacks);
+        }
+        if let Some(coord) = highlights.selected {
+            self.fill_cell(frame, coord, palette.selection);
+        }
+        if let Some((from, to)) = highlights.last_move {
+            for coord in [from, to] {
+                frame.stroke(
+                    &Path::rectangle(
+                        Self::cell_origin(coord) + Vector::new(4.0, 4.0),
+                        Size::new(CELL_SIZE - 8.0, CELL_SIZE - 8.0),
+                    ),
+                    Stroke::default().with_color(palette.last_move).with_width(2.0),
+                );
+            }
+        }
+        if let Some((from, to)) = highlights.hint {
+            // Casas translúcidas por baixo, seta opaca por cima
+            let tint = palette.hint.scale_alpha(0.35);
+            self.fill_cell(frame, from, tint);
+            self.fill_cell(frame, to, tint);
+            draw_arrow(frame, Self::cell_center(from), Self::cell_center(to), palette.hint);
+        }
+        if let Some(coord) = highlights.cursor {
+            frame.stroke(
+                &Path::rectangle(
+                    Self::cell_origin(coord) + Vector::new(1.5, 1.5),
+                    Size::new(CELL_SIZE - 3.0, CELL_SIZE - 3.0),
+                ),
+                Stroke::default()
+                    .with_color(palette.cursor)
+                    .with_width(3.0),
+            );
+        }
+    }
+}
+
+impl canvas::Program<Message> for BoardCanvas<'_> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (canvas::event::Status, Option<Message>) {
+        if let canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(coord) = cursor.position_in(bounds).and_then(|point| self.cell_at(point)) {
+                return (canvas::event::Status::Captured, Some(Message::CellClicked(coord)));
+            }
+        }
+
+        (canvas::event::Status::Ignored, None)
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        // Destaques diferentes dos já desenhados: só essa camada é refeita
+        let mut drawn = self.cache.drawn_highlights.borrow_mut();
+        if drawn.as_ref() != Some(&self.highlights) {
+            self.cache.overlays.clear();
+            *drawn = Some(self.highlights.clone());
+        }
+
+        let board = self.cache.board.draw(renderer, bounds.size(), |frame| self.draw_board(frame));
+        let overlays = self
+            .cache
+            .overlays
+            .draw(renderer, bounds.size(), |frame| self.draw_overlays(frame));
+
+        match self.animation {
+            Some(animation) => {
+                let mut frame = Frame::new(renderer, bounds.size());
+                self.draw_animation(&mut frame, animation);
+                vec![board, frame.into_geometry(), overlays]
+            }
+            None => vec![board, overlays],
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        match cursor.position_in(bounds).and_then(|point| self.cell_at(point)) {
+            Some(_) => mouse::Interaction::Pointer,
+            None => mouse::Interaction::default(),
+        }
+    }
+}
+
+/// Símbolo de uma unidade (ou o ponto de casa vazia)
+fn glyph(symbol: char, position: Point, color: Color) -> canvas::Text {
+    canvas::Text {
+        content: symbol.to_string(),
+        position,
+        color,
+        size: 22.0.into(),
+        horizontal_alignment: alignment::Horizontal::Center,
+        vertical_alignment: alignment::Vertical::Center,
+        ..canvas::Text::default()
+    }
+}
+
+/// Número de linha ou coluna
+fn label(content: String, position: Point, color: Color) -> canvas::Text {
+    canvas::Text {
+        content,
+        position,
+        color,
+        size: 14.0.into(),
+        horizontal_alignment: alignment::Horizontal::Center,
+        vertical_alignment: alignment::Vertical::Center,
+        ..canvas::Text::default()
+    }
+}
+
+/// Seta de `from` até `to`, com a ponta um pouco antes do centro do destino
+fn draw_arrow(frame: &mut Frame, from: Point, to: Point, color: Color) {
+    let delta = to - from;
+    let length = (delta.x * delta.x + delta.y * delta.y).sqrt();
+    if length < f32::EPSILON {
+        return;
+    }
+
+    let direction = Vector::new(delta.x / length, delta.y / length);
+    let normal = Vector::new(-direction.y, direction.x);
+    let tip = to - direction * (CELL_SIZE / 4.0);
+    let head = 10.0;
+
+    let shaft = Path::line(from, tip);
+    let arrowhead = Path::new(|builder| {
+        builder.move_to(tip);
+        builder.line_to(tip - direction * head + normal * (head / 2.0));
+        builder.line_to(tip - direction * head - normal * (head / 2.0));
+        builder.close();
+    });
+
+    frame.stroke(&shaft, Stroke::default().with_color(color).with_width(3.0));
+    frame.fill(&arrowhead, color);
+}
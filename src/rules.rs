@@ -0,0 +1,142 @@
+//! Consultas às regras do `tatic_lib` a partir do estado local
+//!
+//! O cliente não reimplementa as regras: uma ação é legal se a engine aceita
+//! aplicá-la numa cópia do `GameState`.
+
+use tatic_lib::{Action, Board, Coord, GamePhase, GameState};
+
+/// Largura e altura do tabuleiro
+///
+/// Vêm do próprio `Board`: sondar as casas falharia em mapas com buracos na
+/// primeira linha ou coluna.
+pub fn board_size(board: &Board) -> (usize, usize) {
+    (board.width(), board.height())
+}
+
+/// Todas as coordenadas do tabuleiro, linha a linha
+pub fn coords(board: &Board) -> impl Iterator<Item = Coord> {
+    let (width, height) = board_size(board);
+    (0..height).flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+}
+
+/// A engine encerrou a partida
+pub fn is_game_over(state: &GameState) -> bool {
+    matches!(state.phase, GamePhase::Finished { .. })
+}
+
+/// Vencedor declarado pela engine ao encerrar a partida
+pub fn winner(state: &GameState) -> Option<&str> {
+    match &state.phase {
+        GamePhase::Finished { winner, .. } => winner.as_deref(),
+        _ => None,
+    }
+}
+
+/// Dono da unidade em `coord`, se houver
+pub fn owner_at<'a>(state: &'a GameState, coord: &Coord) -> Option<&'a str> {
+    state
+        .board
+        .get(coord)
+        .and_then(|cell| cell.get_unit())
+        .map(|unit| unit.owner.as_str())
+}
+
+/// Tenta aplicar a ação numa cópia do estado
+pub fn try_apply(state: &GameState, player_id: &str, action: &Action) -> Result<GameState, String> {
+    let mut next = state.clone();
+    next.apply_action(player_id, action.clone())
+        .map_err(|e| e.to_string())?;
+    Ok(next)
+}
+
+/// Verifica a ação antes de enviá-la, com um motivo específico quando
+/// inválida; o que escapa às verificações simples fica com a engine
+pub fn validate(state: &GameState, player_id: &str, action: &Action) -> Result<(), String> {
+    if state.turn != player_id {
+        return Err(format!("não é o seu turno (vez de {})", state.turn));
+    }
+
+    if let Action::Move { from, to } | Action::Attack { from, to } = action {
+        match owner_at(state, from) {
+            None => return Err(format!("não há unidade em {}", from)),
+            Some(owner) if owner != player_id => {
+                return Err(format!("a unidade em {} é de {}", from, owner));
+            }
+            Some(_) => {}
+        }
+        if state.board.get(to).is_none() {
+            return Err(format!("{} está fora do tabuleiro", to));
+        }
+
+        match (action, owner_at(state, to)) {
+            (Action::Move { .. }, Some(_)) => return Err(format!("{} está ocupada", to)),
+            (Action::Attack { .. }, None) => return Err(format!("não há inimigo em {}", to)),
+            (Action::Attack { .. }, Some(owner)) if owner == player_id => {
+                return Err("não é possível atacar a própria unidade".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    // Alcance, pontos de ação etc.: a engine explica
+    try_apply(state, player_id, action).map(|_| ())
+}
+
+/// Destinos legais de uma unidade
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Targets {
+    /// Casas vazias alcançáveis
+    pub moves: Vec<Coord>,
+    /// Casas com inimigos ao alcance
+    pub attacks: Vec<Coord>,
+}
+
+/// Destinos legais da unidade de `player_id` em `from`
+pub fn targets(state: &GameState, player_id: &str, from: Coord) -> Targets {
+    let mut targets = Targets::default();
+
+    for (action, _) in unit_actions(state, player_id, from) {
+        match action {
+            Action::Move { to, .. } => targets.moves.push(to),
+            Action::Attack { to, .. } => targets.attacks.push(to),
+            _ => {}
+        }
+    }
+
+    targets
+}
+
+/// Movimentos e ataques aceitos pela engine para a unidade em `from`,
+/// cada um com o estado resultante
+fn unit_actions(state: &GameState, player_id: &str, from: Coord) -> Vec<(Action, GameState)> {
+    coords(&state.board)
+        .filter_map(|to| match owner_at(state, &to) {
+            None => Some(Action::Move { from, to }),
+            Some(owner) if owner != player_id => Some(Action::Attack { from, to }),
+            Some(_) => None,
+        })
+        .filter_map(|action| {
+            let next = try_apply(state, player_id, &action).ok()?;
+            Some((action, next))
+        })
+        .collect()
+}
+
+/// Ações legais de `player_id` com o estado que cada uma produz:
+/// movimentos para casas vazias, ataques a unidades inimigas e o fim de turno
+pub fn legal_actions(state: &GameState, player_id: &str) -> Vec<(Action, GameState)> {
+    let mut actions = Vec::new();
+
+    let own_units: Vec<Coord> = coords(&state.board)
+        .filter(|coord| owner_at(state, coord) == Some(player_id))
+        .collect();
+
+    for from in own_units {
+        actions.extend(unit_actions(state, player_id, from));
+    }
+
+    if let Ok(next) = try_apply(state, player_id, &Action::EndTurn) {
+        actions.push((Action::EndTurn, next));
+    }
+    actions
+}
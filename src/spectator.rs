@@ -0,0 +1,66 @@
+//! Modo espectador: IA contra IA, com controles de reprodução
+
+use iced::{
+    widget::{button, row, text},
+    Alignment, Element,
+};
+
+use crate::Message;
+
+/// Velocidade mínima de reprodução
+pub const MIN_SPEED: f32 = 0.25;
+/// Velocidade máxima de reprodução
+pub const MAX_SPEED: f32 = 8.0;
+
+/// Estado de uma partida IA vs IA assistida
+#[derive(Debug, Clone)]
+pub struct Spectator {
+    /// As duas IAs da partida
+    pub players: [String; 2],
+    /// Reprodução pausada (só avança com "Passo")
+    pub paused: bool,
+    /// Multiplicador da velocidade, de `MIN_SPEED` a `MAX_SPEED`
+    pub speed: f32,
+}
+
+impl Spectator {
+    pub fn new(player1: String, player2: String) -> Self {
+        Self {
+            players: [player1, player2],
+            paused: false,
+            speed: 1.0,
+        }
+    }
+
+    /// Dobra a velocidade, até `MAX_SPEED`
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2.0).min(MAX_SPEED);
+    }
+
+    /// Divide a velocidade por dois, até `MIN_SPEED`
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2.0).max(MIN_SPEED);
+    }
+
+    /// Espera entre jogadas para a velocidade atual
+    pub fn delay_ms(&self, base_ms: u64) -> u64 {
+        (base_ms as f32 / self.speed).round() as u64
+    }
+
+    /// Botões de reprodução
+    pub fn controls_view(&self, game_over: bool) -> Element<'_, Message> {
+        row![
+            text(format!("{} vs {}", self.players[0], self.players[1])),
+            button(if self.paused { "▶ Continuar" } else { "⏸ Pausar" })
+                .on_press_maybe((!game_over).then_some(Message::SpectatorTogglePause)),
+            button("⏭ Passo")
+                .on_press_maybe((self.paused && !game_over).then_some(Message::SpectatorStep)),
+            button("-").on_press_maybe((self.speed > MIN_SPEED).then_some(Message::SpectatorSlower)),
+            text(format!("{}x", self.speed)),
+            button("+").on_press_maybe((self.speed < MAX_SPEED).then_some(Message::SpectatorFaster)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+}
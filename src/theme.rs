@@ -0,0 +1,208 @@
+//! Paletas de cores do cliente
+//!
+//! Cada paleta define o tema do iced e as cores do tabuleiro: casas, grade,
+//! seleção, destaques, cursor e uma cor por jogador.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use iced::{Color, Theme};
+use serde::{Deserialize, Serialize};
+
+/// Paletas disponíveis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaletteName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// Cores distinguíveis com daltonismo (Okabe-Ito)
+    Colorblind,
+}
+
+impl PaletteName {
+    pub const ALL: [PaletteName; 4] = [
+        PaletteName::Dark,
+        PaletteName::Light,
+        PaletteName::HighContrast,
+        PaletteName::Colorblind,
+    ];
+
+    /// Nome usado no arquivo de configuração
+    pub fn key(self) -> &'static str {
+        match self {
+            PaletteName::Dark => "dark",
+            PaletteName::Light => "light",
+            PaletteName::HighContrast => "high_contrast",
+            PaletteName::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            PaletteName::Dark => Palette {
+                theme: Theme::Dark,
+                cell_even: rgb(0x38, 0x38, 0x38),
+                cell_odd: rgb(0x45, 0x45, 0x45),
+                grid: rgb(0x20, 0x20, 0x20),
+                label: rgb(0xb3, 0xb3, 0xb3),
+                empty: rgb(0x80, 0x80, 0x80),
+                selection: rgba(0x33, 0x99, 0xff, 0.45),
+                moves: rgba(0x4d, 0xb3, 0x66, 0.35),
+                attacks: rgba(0xd9, 0x40, 0x40, 0.45),
+                hint: rgb(0xff, 0xbf, 0x33),
+                last_move: rgb(0xd0, 0xd0, 0xd0),
+                cursor: rgb(0xff, 0xe6, 0x00),
+                hp_full: rgb(0x33, 0xcc, 0x33),
+                hp_empty: rgb(0x66, 0x1a, 0x1a),
+                players: [rgb(0x5c, 0xb8, 0xff), rgb(0xff, 0x70, 0x70), rgb(0xc0, 0x8c, 0xff), rgb(0x7d, 0xe0, 0x7d)],
+            },
+            PaletteName::Light => Palette {
+                theme: Theme::Light,
+                cell_even: rgb(0xf0, 0xea, 0xd6),
+                cell_odd: rgb(0xdc, 0xd3, 0xb8),
+                grid: rgb(0xa8, 0x9f, 0x88),
+                label: rgb(0x55, 0x55, 0x55),
+                empty: rgb(0xa0, 0x98, 0x80),
+                selection: rgba(0x1f, 0x6f, 0xd0, 0.35),
+                moves: rgba(0x2e, 0x8b, 0x57, 0.30),
+                attacks: rgba(0xc0, 0x30, 0x30, 0.35),
+                hint: rgb(0xd9, 0x80, 0x00),
+                last_move: rgb(0x70, 0x60, 0x40),
+                cursor: rgb(0x20, 0x20, 0x20),
+                hp_full: rgb(0x2e, 0x8b, 0x2e),
+                hp_empty: rgb(0xcc, 0x99, 0x99),
+                players: [rgb(0x1a, 0x4f, 0xa0), rgb(0xb0, 0x20, 0x20), rgb(0x6a, 0x2c, 0xa0), rgb(0x1e, 0x7a, 0x3a)],
+            },
+            PaletteName::HighContrast => Palette {
+                theme: Theme::Dark,
+                cell_even: rgb(0x00, 0x00, 0x00),
+                cell_odd: rgb(0x14, 0x14, 0x14),
+                grid: rgb(0xff, 0xff, 0xff),
+                label: rgb(0xff, 0xff, 0xff),
+                empty: rgb(0xc0, 0xc0, 0xc0),
+                selection: rgba(0x00, 0xff, 0xff, 0.55),
+                moves: rgba(0x00, 0xff, 0x00, 0.45),
+                attacks: rgba(0xff, 0x00, 0xff, 0.55),
+                hint: rgb(0xff, 0xff, 0x00),
+                last_move: rgb(0xff, 0x80, 0x00),
+                cursor: rgb(0xff, 0xff, 0xff),
+                hp_full: rgb(0x00, 0xff, 0x00),
+                hp_empty: rgb(0xff, 0x00, 0x00),
+                players: [rgb(0xff, 0xff, 0x00), rgb(0x00, 0xff, 0xff), rgb(0xff, 0x80, 0x00), rgb(0xff, 0x00, 0xff)],
+            },
+            PaletteName::Colorblind => Palette {
+                theme: Theme::Dark,
+                cell_even: rgb(0x33, 0x33, 0x33),
+                cell_odd: rgb(0x40, 0x40, 0x40),
+                grid: rgb(0x1a, 0x1a, 0x1a),
+                label: rgb(0xb3, 0xb3, 0xb3),
+                empty: rgb(0x80, 0x80, 0x80),
+                selection: rgba(0x56, 0xb4, 0xe9, 0.45),
+                moves: rgba(0x00, 0x9e, 0x73, 0.40),
+                attacks: rgba(0xd5, 0x5e, 0x00, 0.50),
+                hint: rgb(0xf0, 0xe4, 0x42),
+                last_move: rgb(0xcc, 0x79, 0xa7),
+                cursor: rgb(0xff, 0xff, 0xff),
+                hp_full: rgb(0x00, 0x9e, 0x73),
+                hp_empty: rgb(0x5a, 0x3a, 0x1a),
+                players: [rgb(0x00, 0x72, 0xb2), rgb(0xe6, 0x9f, 0x00), rgb(0xcc, 0x79, 0xa7), rgb(0x56, 0xb4, 0xe9)],
+            },
+        }
+    }
+}
+
+impl fmt::Display for PaletteName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PaletteName::Dark => "Escuro",
+            PaletteName::Light => "Claro",
+            PaletteName::HighContrast => "Alto contraste",
+            PaletteName::Colorblind => "Daltônico",
+        })
+    }
+}
+
+/// Cores efetivas de uma paleta
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    /// Tema dos widgets do iced
+    pub theme: Theme,
+    /// Casas alternadas do tabuleiro
+    pub cell_even: Color,
+    pub cell_odd: Color,
+    pub grid: Color,
+    /// Números de linha e coluna
+    pub label: Color,
+    /// Ponto das casas vazias
+    pub empty: Color,
+    pub selection: Color,
+    pub moves: Color,
+    pub attacks: Color,
+    pub hint: Color,
+    /// Contorno da origem e do destino da última ação
+    pub last_move: Color,
+    pub cursor: Color,
+    /// Extremos da barra de vida
+    pub hp_full: Color,
+    pub hp_empty: Color,
+    /// Cores dos jogadores, na ordem da partida
+    pub players: [Color; 4],
+}
+
+impl Palette {
+    /// Cor do jogador na posição `index` da lista de jogadores da partida
+    pub fn owner_color(&self, index: usize) -> Color {
+        self.players[index % self.players.len()]
+    }
+
+    /// Cor da barra de vida para a fração restante
+    pub fn hp_color(&self, ratio: f32) -> Color {
+        let mix = |full: f32, empty: f32| empty + (full - empty) * ratio;
+        Color::from_rgb(
+            mix(self.hp_full.r, self.hp_empty.r),
+            mix(self.hp_full.g, self.hp_empty.g),
+            mix(self.hp_full.b, self.hp_empty.b),
+        )
+    }
+}
+
+/// Cor de cada dono de unidade, calculada uma vez por estado
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OwnerColors {
+    colors: BTreeMap<String, Color>,
+    /// Dono desconhecido (não deveria acontecer)
+    fallback: Color,
+}
+
+impl OwnerColors {
+    /// Uma cor por dono, na ordem em que aparecem em `owners`: os jogadores
+    /// da partida primeiro mantêm as cores do início ao fim
+    pub fn new<'a>(palette: &Palette, owners: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut colors = BTreeMap::new();
+        for owner in owners {
+            let index = colors.len();
+            colors
+                .entry(owner.to_string())
+                .or_insert_with(|| palette.owner_color(index));
+        }
+
+        Self {
+            colors,
+            fallback: palette.label,
+        }
+    }
+
+    pub fn get(&self, owner: &str) -> Color {
+        self.colors.get(owner).copied().unwrap_or(self.fallback)
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::from_rgb8(r, g, b)
+}
+
+fn rgba(r: u8, g: u8, b: u8, a: f32) -> Color {
+    Color::from_rgba8(r, g, b, a)
+}
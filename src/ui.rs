@@ -3,6 +3,8 @@ use iced::{
     Alignment,
 };
 
+use crate::keymap::Keymap;
+
 /// Cria painel de informações do jogador
 pub fn player_info_panel<'a>(
     player_name: &'a str,
@@ -40,6 +42,31 @@ pub fn game_controls<'a>() -> iced::widget::Row<'a, crate::Message> {
     .spacing(5)
 }
 
+/// Cria painel de ajuda com os atalhos configurados
+pub fn help_overlay<'a>(keymap: &Keymap) -> iced::widget::Container<'a, crate::Message> {
+    let lines = keymap.bindings().map(|(chord, command)| {
+        row![
+            text(chord.to_string()).size(14).width(120),
+            text(command.label()).size(14),
+        ]
+        .spacing(10)
+        .into()
+    });
+    
+    container(
+        column![
+            text("Atalhos").size(18),
+            column(lines).spacing(4),
+            text("Shift + selecionar/clique: forçar ataque").size(14),
+            text("Ctrl + selecionar/clique: forçar movimento").size(14),
+            button("Fechar").on_press(crate::Message::ToggleHelp),
+        ]
+        .spacing(10),
+    )
+    .padding(15)
+    .style(container::rounded_box)
+}
+
 /// Cria indicador de status da conexão
 pub fn connection_indicator<'a>(is_connected: bool) -> iced::widget::Container<'a, crate::Message> {
     let (icon, text_str) = if is_connected {
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream, StreamExt};
+use iced::{stream, Subscription};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue, StatusCode};
+use tokio_tungstenite::{connect_async, tungstenite};
+use tracing::{debug, info, warn};
+
+use crate::Message;
+
+/// Espera inicial antes da primeira tentativa de reconexão
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Espera máxima entre tentativas de reconexão
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Assina as atualizações ao vivo de uma partida via WebSocket
+///
+/// A URL e o token identificam a assinatura: enquanto não mudarem, o Iced
+/// mantém a mesma conexão aberta entre chamadas de `subscription()`. Se a
+/// conexão cair, a assinatura tenta reconectar com backoff exponencial.
+pub fn subscribe(url: String, token: Option<String>) -> Subscription<Message> {
+    Subscription::run_with_id(
+        (url.clone(), token.clone()),
+        stream::channel(100, move |mut output| async move {
+            let mut attempt: u32 = 0;
+
+            loop {
+                info!("🔌 Abrindo WebSocket: {}", url);
+
+                let result = match handshake_request(&url, token.as_deref()) {
+                    Ok(request) => connect_async(request).await,
+                    Err(e) => Err(e),
+                };
+
+                let reason = match result {
+                    Ok((socket, _response)) => {
+                        info!("✅ WebSocket conectado");
+                        attempt = 0;
+
+                        if output.send(Message::WebSocketConnected).await.is_err() {
+                            return;
+                        }
+
+                        match forward_frames(socket, &mut output).await {
+                            Some(reason) => reason,
+                            // Assinatura descartada pelo runtime
+                            None => return,
+                        }
+                    }
+                    Err(tungstenite::Error::Http(response))
+                        if response.status() == StatusCode::UNAUTHORIZED =>
+                    {
+                        // Reconectar não adianta: precisa de um token novo
+                        warn!("⚠️ WebSocket recusou o token");
+                        let _ = output.send(Message::SessionExpired).await;
+                        return;
+                    }
+                    Err(e) => e.to_string(),
+                };
+
+                attempt += 1;
+                warn!("⚠️ WebSocket desconectado ({}), tentativa {}", reason, attempt);
+
+                if output
+                    .send(Message::WebSocketReconnecting(attempt, reason))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+
+                tokio::time::sleep(backoff(attempt)).await;
+            }
+        }),
+    )
+}
+
+/// Monta o handshake com o header `Authorization`, se houver token
+fn handshake_request(url: &str, token: Option<&str>) -> Result<Request, tungstenite::Error> {
+    let mut request = url.into_client_request()?;
+
+    if let Some(token) = token {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token))?;
+        request.headers_mut().insert(header::AUTHORIZATION, value);
+    }
+
+    Ok(request)
+}
+
+/// Encaminha os frames de texto até a conexão cair
+///
+/// Retorna o motivo da queda, ou `None` se o receptor foi descartado.
+async fn forward_frames<S>(mut socket: S, output: &mut mpsc::Sender<Message>) -> Option<String>
+where
+    S: Stream<Item = Result<tungstenite::Message, tungstenite::Error>> + Unpin,
+{
+    while let Some(frame) = socket.next().await {
+        match frame {
+            Ok(tungstenite::Message::Text(text)) => {
+                debug!("WS <- {}", text.as_str());
+                if output
+                    .send(Message::WebSocketMessage(text.to_string()))
+                    .await
+                    .is_err()
+                {
+                    return None;
+                }
+            }
+            Ok(tungstenite::Message::Close(frame)) => {
+                info!("🔌 WebSocket fechado pelo servidor: {:?}", frame);
+                return Some("Conexão encerrada pelo servidor".to_string());
+            }
+            Ok(_) => {}
+            Err(e) => return Some(e.to_string()),
+        }
+    }
+
+    Some("Conexão encerrada".to_string())
+}
+
+/// Backoff exponencial: 0.5s, 1s, 2s, ... limitado a `MAX_BACKOFF`
+fn backoff(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    INITIAL_BACKOFF.saturating_mul(factor).min(MAX_BACKOFF)
+}